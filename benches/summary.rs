@@ -0,0 +1,91 @@
+use std::alloc::GlobalAlloc;
+use std::alloc::Layout;
+use std::alloc::System;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+
+use criterion::criterion_group;
+use criterion::criterion_main;
+use criterion::Criterion;
+use smaps::Parser;
+use smaps::Snapshot;
+
+/// Tracks allocations and peak heap usage, to compare each approach's
+/// memory footprint alongside its running time.
+struct Counting;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        let current = CURRENT.fetch_add(layout.size(), Ordering::Relaxed) + layout.size();
+        PEAK.fetch_max(current, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        CURRENT.fetch_sub(layout.size(), Ordering::Relaxed);
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+const SAMPLE: &str = include_str!("../tests/data/smaps");
+
+fn collect(smaps: &[u8]) -> usize {
+    Parser::from_reader(smaps)
+        .entries()
+        .collect::<Result<Snapshot, _>>()
+        .unwrap()
+        .total()
+        .pss
+}
+
+fn stream(smaps: &[u8]) -> usize {
+    smaps::summarize(Parser::from_reader(smaps)).unwrap().pss
+}
+
+/// Returns the number of allocations and the peak heap growth of `f`.
+fn measure(f: impl Fn(&[u8]) -> usize, smaps: &[u8]) -> (usize, usize) {
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+    let current = CURRENT.load(Ordering::Relaxed);
+    PEAK.store(current, Ordering::Relaxed);
+    std::hint::black_box(f(smaps));
+    (
+        ALLOCATIONS.load(Ordering::Relaxed) - allocations,
+        PEAK.load(Ordering::Relaxed) - current,
+    )
+}
+
+fn bench(criterion: &mut Criterion) {
+    // Roughly 5000 mappings
+    let smaps = SAMPLE.repeat(100);
+    let smaps = smaps.as_bytes();
+
+    assert_eq!(collect(smaps), stream(smaps));
+    for (name, f) in [
+        ("collect", collect as fn(&[u8]) -> usize),
+        ("stream", stream),
+    ] {
+        let (allocations, peak) = measure(f, smaps);
+        println!(
+            "summary/{}: {} allocations, {} peak",
+            name,
+            allocations,
+            smaps::format_bytes(peak),
+        );
+    }
+
+    let mut group = criterion.benchmark_group("summary");
+    group.bench_function("collect", |bencher| bencher.iter(|| collect(smaps)));
+    group.bench_function("stream", |bencher| bencher.iter(|| stream(smaps)));
+    group.finish();
+}
+
+criterion_group!(benches, bench);
+criterion_main!(benches);
@@ -0,0 +1,55 @@
+use alloc::collections::BTreeMap;
+
+use crate::Mapping;
+use crate::Permissions;
+use crate::RegionKind;
+use crate::Usage;
+
+/// Coarse classification of a mapping for "where is my memory going" reports.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Category {
+    /// `[heap]`
+    Heap,
+    /// `[stack]`, or a `[stack:<tid>]` thread stack on older kernels
+    Stack,
+    /// executable file-backed mapping
+    Code,
+    /// non-executable file-backed mapping
+    FileData,
+    /// no path and inode 0, or a named anonymous mapping (`[anon:...]`)
+    Anonymous,
+    /// any other pseudo-path, e.g. `[vdso]` or `[vsyscall]`
+    Special,
+}
+
+impl Mapping {
+    /// Refines [`Mapping::kind`] with the permissions, so the two always
+    /// agree on what is file-backed and what is anonymous.
+    pub fn category(&self) -> Category {
+        match self.kind() {
+            RegionKind::Heap => Category::Heap,
+            RegionKind::Stack | RegionKind::ThreadStack(_) => Category::Stack,
+            RegionKind::Anonymous => Category::Anonymous,
+            RegionKind::File if self.permissions.contains(Permissions::X) => Category::Code,
+            RegionKind::File => Category::FileData,
+            RegionKind::Vdso | RegionKind::Vvar | RegionKind::Vsyscall | RegionKind::Special => {
+                Category::Special
+            }
+        }
+    }
+}
+
+/// Sums usage per [`Category`].
+pub fn categorize<'a, I>(entries: I) -> BTreeMap<Category, Usage>
+where
+    I: IntoIterator<Item = &'a (Mapping, Usage)>,
+{
+    let mut categories = BTreeMap::<_, Usage>::new();
+    for (mapping, usage) in entries {
+        categories
+            .entry(mapping.category())
+            .or_default()
+            .merge(usage);
+    }
+    categories
+}
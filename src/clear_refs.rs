@@ -0,0 +1,23 @@
+use std::path::Path;
+
+/// Clears the referenced and accessed bits of every page in `pid`, by
+/// writing `1` to `/proc/<pid>/clear_refs`.
+///
+/// Running a workload afterwards and then reading smaps shows the pages it
+/// touched in `Referenced`. The kernel also accepts `2` and `3` to clear
+/// only anonymous or file-backed pages, and `5` to reset the peak RSS.
+pub fn reset_references(pid: u32) -> std::io::Result<()> {
+    write(pid, "1")
+}
+
+/// Clears the soft-dirty bit of every page in `pid`, by writing `4` to
+/// `/proc/<pid>/clear_refs`.
+///
+/// Pages written afterwards set [`crate::VmFlags::SD`] on their mapping.
+pub fn reset_soft_dirty(pid: u32) -> std::io::Result<()> {
+    write(pid, "4")
+}
+
+fn write(pid: u32, value: &str) -> std::io::Result<()> {
+    std::fs::write(Path::new(&format!("/proc/{}/clear_refs", pid)), value)
+}
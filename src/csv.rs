@@ -0,0 +1,55 @@
+use std::io::Write;
+
+use crate::Mapping;
+use crate::Usage;
+
+const HEADER: [&str; 15] = [
+    "start",
+    "end",
+    "size",
+    "permissions",
+    "path",
+    "kind",
+    "rss",
+    "pss",
+    "swap",
+    "shared_clean",
+    "shared_dirty",
+    "private_clean",
+    "private_dirty",
+    "anonymous",
+    "locked",
+];
+
+/// Writes a header row and then one row per region, for importing into a
+/// spreadsheet.
+///
+/// Addresses are `0x`-prefixed hex, sizes are in bytes, and `kind` is the
+/// [`crate::RegionKind`] as formatted by `Display`. Anonymous mappings have
+/// an empty `path`.
+pub fn write_csv<W: Write>(entries: &[(Mapping, Usage)], writer: W) -> std::io::Result<()> {
+    let mut writer = csv::Writer::from_writer(writer);
+    writer.write_record(HEADER)?;
+
+    for (mapping, usage) in entries {
+        writer.write_record([
+            format!("{:#x}", mapping.start),
+            format!("{:#x}", mapping.end),
+            mapping.size().to_string(),
+            mapping.permissions.to_string(),
+            mapping.path.clone().unwrap_or_default(),
+            mapping.kind().to_string(),
+            usage.rss.to_string(),
+            usage.pss.to_string(),
+            usage.swap.to_string(),
+            usage.shared_clean.to_string(),
+            usage.shared_dirty.to_string(),
+            usage.private_clean.to_string(),
+            usage.private_dirty.to_string(),
+            usage.anonymous.to_string(),
+            usage.locked.to_string(),
+        ])?;
+    }
+
+    writer.flush()
+}
@@ -0,0 +1,80 @@
+use crate::Usage;
+use crate::VmFlags;
+
+/// Signed per-field difference between two samples of the same mapping, in bytes.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct UsageDelta {
+    pub size: i64,
+    pub kernel_page_size: i64,
+    pub mmu_page_size: i64,
+    pub rss: i64,
+    pub pss: i64,
+    pub pss_dirty: i64,
+    pub shared_clean: i64,
+    pub shared_dirty: i64,
+    pub private_clean: i64,
+    pub private_dirty: i64,
+    pub referenced: i64,
+    pub anonymous: i64,
+    pub ksm: i64,
+    pub lazy_free: i64,
+    pub anon_huge_pages: i64,
+    pub shmem_huge_pages: i64,
+    pub shmem_pmd_mapped: i64,
+    pub file_pmd_mapped: i64,
+    pub shared_hugetlb: i64,
+    pub private_hugetlb: i64,
+    pub swap: i64,
+    pub swap_pss: i64,
+    pub locked: i64,
+    /// `THPeligible` differs between the two samples
+    pub thp_eligible_changed: bool,
+    /// `ProtectionKey` differs between the two samples
+    pub protection_key_changed: bool,
+    /// flags set in the later sample but not the earlier one
+    pub vm_flags_added: VmFlags,
+    /// flags set in the earlier sample but not the later one
+    pub vm_flags_removed: VmFlags,
+}
+
+impl Usage {
+    /// Computes `self - earlier` field by field.
+    pub fn delta(&self, earlier: &Usage) -> UsageDelta {
+        fn sub(later: usize, earlier: usize) -> i64 {
+            later as i64 - earlier as i64
+        }
+
+        let later_flags = self.vm_flags.unwrap_or_default();
+        let earlier_flags = earlier.vm_flags.unwrap_or_default();
+
+        UsageDelta {
+            size: sub(self.size, earlier.size),
+            kernel_page_size: sub(self.kernel_page_size, earlier.kernel_page_size),
+            mmu_page_size: sub(self.mmu_page_size, earlier.mmu_page_size),
+            rss: sub(self.rss, earlier.rss),
+            pss: sub(self.pss, earlier.pss),
+            pss_dirty: sub(self.pss_dirty, earlier.pss_dirty),
+            shared_clean: sub(self.shared_clean, earlier.shared_clean),
+            shared_dirty: sub(self.shared_dirty, earlier.shared_dirty),
+            private_clean: sub(self.private_clean, earlier.private_clean),
+            private_dirty: sub(self.private_dirty, earlier.private_dirty),
+            referenced: sub(self.referenced, earlier.referenced),
+            anonymous: sub(self.anonymous, earlier.anonymous),
+            ksm: sub(self.ksm, earlier.ksm),
+            lazy_free: sub(self.lazy_free, earlier.lazy_free),
+            anon_huge_pages: sub(self.anon_huge_pages, earlier.anon_huge_pages),
+            shmem_huge_pages: sub(self.shmem_huge_pages, earlier.shmem_huge_pages),
+            shmem_pmd_mapped: sub(self.shmem_pmd_mapped, earlier.shmem_pmd_mapped),
+            file_pmd_mapped: sub(self.file_pmd_mapped, earlier.file_pmd_mapped),
+            shared_hugetlb: sub(self.shared_hugetlb, earlier.shared_hugetlb),
+            private_hugetlb: sub(self.private_hugetlb, earlier.private_hugetlb),
+            swap: sub(self.swap, earlier.swap),
+            swap_pss: sub(self.swap_pss, earlier.swap_pss),
+            locked: sub(self.locked, earlier.locked),
+            thp_eligible_changed: self.thp_eligible != earlier.thp_eligible,
+            protection_key_changed: self.protection_key != earlier.protection_key,
+            vm_flags_added: later_flags.difference(earlier_flags),
+            vm_flags_removed: earlier_flags.difference(later_flags),
+        }
+    }
+}
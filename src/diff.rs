@@ -0,0 +1,250 @@
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::format::format_bytes;
+use crate::Mapping;
+use crate::Snapshot;
+use crate::Usage;
+use crate::UsageDelta;
+
+/// How [`diff`] pairs up regions between two snapshots.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum MatchBy {
+    /// Same start address, for samples of one process over time. Regions
+    /// that grow or shrink in place (like `[heap]`) still match.
+    #[default]
+    Address,
+    /// Same path and file offset, for comparing runs whose addresses differ
+    /// due to ASLR. Anonymous regions never match, so they're reported as
+    /// removed and added.
+    Path,
+}
+
+impl MatchBy {
+    fn key(self, mapping: &Mapping) -> Option<(Option<&str>, u64)> {
+        match self {
+            MatchBy::Address => Some((None, mapping.start)),
+            MatchBy::Path => Some((Some(mapping.path.as_deref()?), mapping.offset)),
+        }
+    }
+}
+
+/// Differences between an earlier and a later [`Snapshot`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DiffReport {
+    added: Vec<(Mapping, Usage)>,
+    removed: Vec<(Mapping, Usage)>,
+    /// regions present in both, with the later mapping
+    matched: Vec<(Mapping, UsageDelta)>,
+}
+
+/// A region that differs between two lists of regions, as reported by
+/// [`changes`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Change {
+    Added(Mapping, Usage),
+    Removed(Mapping, Usage),
+    /// present in both, with different usage
+    Modified {
+        mapping: Mapping,
+        before: Usage,
+        after: Usage,
+    },
+}
+
+/// For each region of `earlier`, the index of the region of `later` with
+/// the same key, if any. If several regions share a key, they're paired in
+/// order.
+fn pair<'a, K: Ord>(
+    earlier: &'a [(Mapping, Usage)],
+    later: &'a [(Mapping, Usage)],
+    key: impl Fn(&'a Mapping) -> Option<K>,
+) -> Vec<Option<usize>> {
+    let mut unmatched = BTreeMap::<_, Vec<_>>::new();
+    for (index, (mapping, _)) in later.iter().enumerate().rev() {
+        if let Some(key) = key(mapping) {
+            unmatched.entry(key).or_default().push(index);
+        }
+    }
+
+    earlier
+        .iter()
+        .map(|(mapping, _)| key(mapping).and_then(|key| unmatched.get_mut(&key)?.pop()))
+        .collect()
+}
+
+/// Regions of `later` that weren't paired with any region of `earlier`.
+fn unpaired<'a>(
+    later: &'a [(Mapping, Usage)],
+    pairs: &[Option<usize>],
+) -> impl Iterator<Item = &'a (Mapping, Usage)> {
+    let mut paired = vec![false; later.len()];
+    for index in pairs.iter().flatten() {
+        paired[*index] = true;
+    }
+
+    later
+        .iter()
+        .zip(paired)
+        .filter(|(_, paired)| !paired)
+        .map(|(region, _)| region)
+}
+
+/// Compares `earlier` against `later`, pairing regions according to
+/// `match_by`. If several regions share a key, they're paired in order.
+pub fn diff(earlier: &Snapshot, later: &Snapshot, match_by: MatchBy) -> DiffReport {
+    let (earlier, later) = (earlier.as_slice(), later.as_slice());
+    let pairs = pair(earlier, later, |mapping| match_by.key(mapping));
+
+    let mut report = DiffReport::default();
+    for ((mapping, usage), index) in earlier.iter().zip(&pairs) {
+        match index {
+            None => report.removed.push((mapping.clone(), usage.clone())),
+            Some(index) => {
+                let (mapping, current) = &later[*index];
+                report.matched.push((mapping.clone(), current.delta(usage)));
+            }
+        }
+    }
+
+    report.added = unpaired(later, &pairs).cloned().collect();
+    report
+}
+
+/// Lists the regions added, removed, or with changed usage between `before`
+/// and `after`, matching regions by start address, end address, and path.
+///
+/// Removed and modified regions come first, in the order of `before`,
+/// followed by added regions in the order of `after`.
+pub fn changes(before: &[(Mapping, Usage)], after: &[(Mapping, Usage)]) -> Vec<Change> {
+    let pairs = pair(before, after, |mapping| {
+        Some((mapping.start, mapping.end, mapping.path.as_deref()))
+    });
+
+    let mut changes = Vec::new();
+    for ((mapping, usage), index) in before.iter().zip(&pairs) {
+        match index {
+            None => changes.push(Change::Removed(mapping.clone(), usage.clone())),
+            Some(index) if after[*index].1 != *usage => changes.push(Change::Modified {
+                mapping: after[*index].0.clone(),
+                before: usage.clone(),
+                after: after[*index].1.clone(),
+            }),
+            Some(_) => {}
+        }
+    }
+
+    changes.extend(
+        unpaired(after, &pairs)
+            .map(|(mapping, usage)| Change::Added(mapping.clone(), usage.clone())),
+    );
+    changes
+}
+
+impl Snapshot {
+    /// Compares `self` against a `later` snapshot, matching regions by
+    /// start address.
+    pub fn diff(&self, later: &Snapshot) -> DiffReport {
+        diff(self, later, MatchBy::Address)
+    }
+
+    pub fn diff_by(&self, later: &Snapshot, match_by: MatchBy) -> DiffReport {
+        diff(self, later, match_by)
+    }
+}
+
+impl DiffReport {
+    /// Regions only in the later snapshot.
+    pub fn added(&self) -> &[(Mapping, Usage)] {
+        &self.added
+    }
+
+    /// Regions only in the earlier snapshot.
+    pub fn removed(&self) -> &[(Mapping, Usage)] {
+        &self.removed
+    }
+
+    /// Matched regions whose PSS increased, largest increase first.
+    pub fn grown(&self) -> Vec<&(Mapping, UsageDelta)> {
+        let mut grown = self
+            .matched
+            .iter()
+            .filter(|(_, delta)| delta.pss > 0)
+            .collect::<Vec<_>>();
+        grown.sort_by_key(|(_, delta)| -delta.pss);
+        grown
+    }
+
+    /// Matched regions whose PSS decreased, largest decrease first.
+    pub fn shrunk(&self) -> Vec<&(Mapping, UsageDelta)> {
+        let mut shrunk = self
+            .matched
+            .iter()
+            .filter(|(_, delta)| delta.pss < 0)
+            .collect::<Vec<_>>();
+        shrunk.sort_by_key(|(_, delta)| delta.pss);
+        shrunk
+    }
+
+    /// Net change in PSS, counting added and removed regions in full.
+    pub fn total_pss_change(&self) -> i64 {
+        let total = |regions: &[(Mapping, Usage)]| {
+            regions
+                .iter()
+                .map(|(_, usage)| usage.pss as i64)
+                .sum::<i64>()
+        };
+
+        total(&self.added) - total(&self.removed)
+            + self.matched.iter().map(|(_, delta)| delta.pss).sum::<i64>()
+    }
+
+    /// Summarizes the report, listing the ten largest growers.
+    pub fn display(&self) -> DiffDisplay<'_> {
+        DiffDisplay { report: self }
+    }
+}
+
+/// A leak-report style summary of a [`DiffReport`], created by
+/// [`DiffReport::display`].
+pub struct DiffDisplay<'a> {
+    report: &'a DiffReport,
+}
+
+impl fmt::Display for DiffDisplay<'_> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        const TOP: usize = 10;
+
+        let signed = |bytes: i64| {
+            let sign = if bytes < 0 { '-' } else { '+' };
+            format!("{}{}", sign, format_bytes(bytes.unsigned_abs() as usize))
+        };
+
+        let grown = self.report.grown();
+        writeln!(
+            fmt,
+            "PSS {} ({} added, {} removed, {} grown, {} shrunk)",
+            signed(self.report.total_pss_change()),
+            self.report.added.len(),
+            self.report.removed.len(),
+            grown.len(),
+            self.report.shrunk().len(),
+        )?;
+
+        for (mapping, delta) in grown.into_iter().take(TOP) {
+            writeln!(
+                fmt,
+                "{:>12}  {:016x}-{:016x}  {}",
+                signed(delta.pss),
+                mapping.start,
+                mapping.end,
+                mapping.path.as_deref().unwrap_or("[anon]"),
+            )?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,130 @@
+use alloc::format;
+use core::fmt;
+
+use crate::line::PATH_COLUMN;
+use crate::Device;
+use crate::Mapping;
+use crate::Permissions;
+use crate::RegionKind;
+use crate::Usage;
+use crate::VmFlags;
+
+impl fmt::Display for Mapping {
+    /// Formats as the kernel's smaps header line, padding the path to the
+    /// same column as on 64-bit systems. Anonymous mappings have no path or
+    /// padding.
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        let header = format!(
+            "{:08x}-{:08x} {} {:08x} {} {}",
+            self.start, self.end, self.permissions, self.offset, self.device, self.inode,
+        );
+
+        let Some(path) = &self.path else {
+            return write!(fmt, "{}", header);
+        };
+
+        let width = PATH_COLUMN - 1;
+        write!(fmt, "{:<width$} {}", header, path)?;
+        if self.deleted {
+            write!(fmt, " (deleted)")?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Usage {
+    /// Formats as the body of an smaps entry, matching the kernel's key
+    /// order and column alignment. Sizes are printed in kB.
+    ///
+    /// Parsing a mapping's header followed by this output gives back an
+    /// equal `Usage`, as long as sizes are whole kB (as they are when
+    /// parsed from smaps). Every size field is printed, even if the kernel
+    /// that produced the original didn't report it.
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        for (key, value) in self.sizes() {
+            let width = 23 - key.len();
+            writeln!(fmt, "{}:{:>width$} kB", key, value >> 10)?;
+        }
+
+        if let Some(eligible) = self.thp_eligible {
+            writeln!(fmt, "{:<16}{:>8}", "THPeligible:", eligible as u8)?;
+        }
+
+        if let Some(key) = self.protection_key {
+            writeln!(fmt, "{:<16}{:>8}", "ProtectionKey:", key)?;
+        }
+
+        if let Some(vm_flags) = self.vm_flags {
+            write!(fmt, "VmFlags: ")?;
+            for (name, _) in vm_flags.iter_names() {
+                write!(fmt, "{} ", name.to_ascii_lowercase())?;
+            }
+            for name in &self.unknown_vm_flags {
+                write!(fmt, "{} ", name)?;
+            }
+            writeln!(fmt)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for Permissions {
+    /// Formats as in the smaps header, e.g. `r-xp`.
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        for (permission, set) in [
+            (Permissions::R, 'r'),
+            (Permissions::W, 'w'),
+            (Permissions::X, 'x'),
+        ] {
+            let char = if self.contains(permission) { set } else { '-' };
+            write!(fmt, "{}", char)?;
+        }
+
+        let shared = if self.contains(Permissions::S) {
+            's'
+        } else {
+            'p'
+        };
+        write!(fmt, "{}", shared)
+    }
+}
+
+impl fmt::Display for Device {
+    /// Formats as in the smaps header, e.g. `fd:00`.
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(fmt, "{:02x}:{:02x}", self.major, self.minor)
+    }
+}
+
+impl fmt::Display for VmFlags {
+    /// Formats as the lowercase two-letter tokens of the `VmFlags` line,
+    /// separated by spaces, in the kernel's order, e.g. `rd ex mr mw me`.
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        for (index, (name, _)) in self.iter_names().enumerate() {
+            if index > 0 {
+                write!(fmt, " ")?;
+            }
+            write!(fmt, "{}", name.to_ascii_lowercase())?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for RegionKind {
+    /// Formats as a lowercase name, e.g. `heap`, or `stack:<tid>` for a
+    /// thread stack.
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RegionKind::Heap => write!(fmt, "heap"),
+            RegionKind::Stack => write!(fmt, "stack"),
+            RegionKind::ThreadStack(tid) => write!(fmt, "stack:{}", tid),
+            RegionKind::Vdso => write!(fmt, "vdso"),
+            RegionKind::Vvar => write!(fmt, "vvar"),
+            RegionKind::Vsyscall => write!(fmt, "vsyscall"),
+            RegionKind::Anonymous => write!(fmt, "anonymous"),
+            RegionKind::File => write!(fmt, "file"),
+            RegionKind::Special => write!(fmt, "special"),
+        }
+    }
+}
@@ -0,0 +1,127 @@
+use std::io::BufRead;
+
+use crate::parse::ParseMapping;
+use crate::Mapping;
+use crate::Parser;
+use crate::Permissions;
+use crate::SmapsError;
+use crate::Usage;
+
+/// Lazy iterator over the regions of a [`Parser`], created by
+/// [`Parser::entries`].
+///
+/// Only the current line is buffered, so arbitrarily large streams can be
+/// parsed in constant memory. Iteration ends after the first error.
+pub struct Entries<R: BufRead> {
+    parser: Option<Parser<R, ParseMapping>>,
+    /// bits a mapping's permissions must contain to be yielded
+    permissions: Permissions,
+    /// predicate a mapping's path must satisfy to be yielded
+    path: Option<PathFilter>,
+}
+
+type PathFilter = Box<dyn Fn(&str) -> bool + Send>;
+
+/// Lazy iterator over the mapping headers of a [`Parser`], created by
+/// [`Parser::mappings_only`].
+pub struct Mappings<R: BufRead> {
+    parser: Option<Parser<R, ParseMapping>>,
+}
+
+impl<R: BufRead> Parser<R, ParseMapping> {
+    pub fn entries(self) -> Entries<R> {
+        Entries {
+            parser: Some(self),
+            permissions: Permissions::empty(),
+            path: None,
+        }
+    }
+
+    /// Yields only the mapping headers, skipping each usage block without
+    /// parsing it, like reading `/proc/<pid>/maps` from an smaps file.
+    pub fn mappings_only(self) -> Mappings<R> {
+        Mappings { parser: Some(self) }
+    }
+}
+
+impl<R: BufRead> Entries<R> {
+    /// Only yield mappings whose permissions contain all of `permissions`,
+    /// e.g. `Permissions::R | Permissions::X` for loaded code.
+    ///
+    /// The usage blocks of other mappings are skipped without being parsed,
+    /// so they aren't checked even by [`Parser::strict`].
+    pub fn filter_permissions(mut self, permissions: Permissions) -> Self {
+        self.permissions = permissions;
+        self
+    }
+
+    /// Only yield mappings whose path satisfies `predicate`, e.g.
+    /// `|path| path.ends_with("libc.so.6")`. Anonymous mappings are
+    /// excluded.
+    ///
+    /// As with [`Entries::filter_permissions`], the usage blocks of other
+    /// mappings are skipped without being parsed.
+    pub fn filter_path(mut self, predicate: impl Fn(&str) -> bool + Send + 'static) -> Self {
+        self.path = Some(Box::new(predicate));
+        self
+    }
+
+    fn matches(&self, mapping: &Mapping) -> bool {
+        mapping.permissions.contains(self.permissions)
+            && self
+                .path
+                .as_ref()
+                .is_none_or(|predicate| mapping.path.as_deref().is_some_and(predicate))
+    }
+}
+
+impl<R: BufRead> IntoIterator for Parser<R, ParseMapping> {
+    type Item = Result<(Mapping, Usage), SmapsError>;
+    type IntoIter = Entries<R>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries()
+    }
+}
+
+impl<R: BufRead> Iterator for Entries<R> {
+    type Item = Result<(Mapping, Usage), SmapsError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut parser = self.parser.take()?;
+        let (parser, mapping) = loop {
+            match parser.next() {
+                Ok((next, Some(mapping))) if !self.matches(&mapping) => {
+                    parser = next.skip();
+                }
+                Ok((next, Some(mapping))) => break (next, mapping),
+                Ok((_, None)) => return None,
+                Err(error) => return Some(Err(error)),
+            }
+        };
+
+        match parser.next() {
+            Ok((parser, Some(usage))) => {
+                self.parser = Some(parser);
+                Some(Ok((mapping, usage)))
+            }
+            Ok((_, None)) => None,
+            Err(error) => Some(Err(error)),
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for Mappings<R> {
+    type Item = Result<Mapping, SmapsError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.parser.take()?.next() {
+            Ok((parser, Some(mapping))) => {
+                self.parser = Some(parser.skip());
+                Some(Ok(mapping))
+            }
+            Ok((_, None)) => None,
+            Err(error) => Some(Err(error)),
+        }
+    }
+}
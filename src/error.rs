@@ -0,0 +1,124 @@
+use alloc::string::String;
+use core::fmt;
+
+#[derive(Debug)]
+pub enum SmapsError {
+    #[cfg(feature = "std")]
+    Io(std::io::Error),
+    /// not allowed to read another user's process (`EACCES`)
+    #[cfg(feature = "std")]
+    PermissionDenied(std::io::Error),
+    /// the process exited before or while it was read (`ESRCH`, or `ENOENT`
+    /// from [`crate::Parser::from_pid`])
+    #[cfg(feature = "std")]
+    ProcessGone(std::io::Error),
+    UnknownKey(String),
+    UnknownUnit(String),
+    UnknownVmFlag(String),
+    /// unexpected character in a mapping's permissions, at `position`
+    InvalidPermissions {
+        permissions: String,
+        position: usize,
+    },
+    /// permission bits with unknown flags, or not exactly one of shared and
+    /// private set
+    InvalidPermissionBits(u8),
+    /// mapping header cut off before a required field
+    MissingField {
+        field: &'static str,
+        line: String,
+    },
+    /// mapping header with a required field that doesn't parse or an end
+    /// address below its start, or a `ProtectionKey` outside of
+    /// `0..=Usage::MAX_PROTECTION_KEY`
+    InvalidField {
+        field: &'static str,
+        line: String,
+    },
+    /// hex address or offset too large for `u64`, or size too large for
+    /// `usize` once scaled by its unit
+    Overflow(String),
+    /// line that's neither a mapping header nor a usage line, numbered
+    /// from 1
+    Malformed {
+        line_number: usize,
+        line: String,
+    },
+}
+
+impl fmt::Display for SmapsError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            #[cfg(feature = "std")]
+            SmapsError::Io(error) => write!(fmt, "{}", error),
+            #[cfg(feature = "std")]
+            SmapsError::PermissionDenied(error) => write!(fmt, "Permission denied: {}", error),
+            #[cfg(feature = "std")]
+            SmapsError::ProcessGone(error) => write!(fmt, "Process gone: {}", error),
+            SmapsError::UnknownKey(key) => write!(fmt, "Unrecognized key: {}", key),
+            SmapsError::UnknownUnit(unit) => write!(fmt, "Unrecognized unit: {}", unit),
+            SmapsError::UnknownVmFlag(flag) => write!(fmt, "Unrecognized VM flag: {}", flag),
+            SmapsError::InvalidPermissions {
+                permissions,
+                position,
+            } => write!(
+                fmt,
+                "Invalid permissions at position {}: {}",
+                position, permissions
+            ),
+            SmapsError::InvalidPermissionBits(bits) => {
+                write!(fmt, "Invalid permission bits: {:#07b}", bits)
+            }
+            SmapsError::MissingField { field, line } => {
+                write!(fmt, "Missing {} in mapping header: {}", field, line)
+            }
+            SmapsError::InvalidField { field, line } => {
+                write!(fmt, "Invalid {} in mapping header: {}", field, line)
+            }
+            SmapsError::Overflow(value) => write!(fmt, "Value overflows: {}", value),
+            SmapsError::Malformed { line_number, line } => {
+                write!(fmt, "Malformed line {}: {}", line_number, line)
+            }
+        }
+    }
+}
+
+impl core::error::Error for SmapsError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            #[cfg(feature = "std")]
+            SmapsError::Io(error)
+            | SmapsError::PermissionDenied(error)
+            | SmapsError::ProcessGone(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+/// `ESRCH` ("no such process") on Linux, which has no `ErrorKind` of its
+/// own. Hardcoded so that classifying errors doesn't require `libc`.
+#[cfg(feature = "std")]
+const ESRCH: i32 = 3;
+
+#[cfg(feature = "std")]
+impl From<std::io::Error> for SmapsError {
+    fn from(error: std::io::Error) -> Self {
+        match error.kind() {
+            std::io::ErrorKind::PermissionDenied => SmapsError::PermissionDenied(error),
+            _ if error.raw_os_error() == Some(ESRCH) => SmapsError::ProcessGone(error),
+            _ => SmapsError::Io(error),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl SmapsError {
+    /// Classifies an error from opening a file under `/proc/<pid>`, where
+    /// a missing file means the process has exited.
+    pub(crate) fn from_proc_io(error: std::io::Error) -> Self {
+        match error.kind() {
+            std::io::ErrorKind::NotFound => SmapsError::ProcessGone(error),
+            _ => SmapsError::from(error),
+        }
+    }
+}
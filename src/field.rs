@@ -0,0 +1,136 @@
+use core::cmp::Ordering;
+
+use crate::Usage;
+
+/// Selects one of [`Usage`]'s size fields, for sorting or ranking by it.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum UsageField {
+    Size,
+    KernelPageSize,
+    MmuPageSize,
+    Rss,
+    Pss,
+    PssDirty,
+    SharedClean,
+    SharedDirty,
+    PrivateClean,
+    PrivateDirty,
+    Referenced,
+    Anonymous,
+    Ksm,
+    LazyFree,
+    AnonHugePages,
+    ShmemPmdMapped,
+    FilePmdMapped,
+    SharedHugetlb,
+    PrivateHugetlb,
+    Swap,
+    SwapPss,
+    Locked,
+}
+
+impl UsageField {
+    /// The field's smaps key, e.g. `"Pss_Dirty"`.
+    pub fn key(self) -> &'static str {
+        match self {
+            UsageField::Size => "Size",
+            UsageField::KernelPageSize => "KernelPageSize",
+            UsageField::MmuPageSize => "MMUPageSize",
+            UsageField::Rss => "Rss",
+            UsageField::Pss => "Pss",
+            UsageField::PssDirty => "Pss_Dirty",
+            UsageField::SharedClean => "Shared_Clean",
+            UsageField::SharedDirty => "Shared_Dirty",
+            UsageField::PrivateClean => "Private_Clean",
+            UsageField::PrivateDirty => "Private_Dirty",
+            UsageField::Referenced => "Referenced",
+            UsageField::Anonymous => "Anonymous",
+            UsageField::Ksm => "KSM",
+            UsageField::LazyFree => "LazyFree",
+            UsageField::AnonHugePages => "AnonHugePages",
+            UsageField::ShmemPmdMapped => "ShmemPmdMapped",
+            UsageField::FilePmdMapped => "FilePmdMapped",
+            UsageField::SharedHugetlb => "Shared_Hugetlb",
+            UsageField::PrivateHugetlb => "Private_Hugetlb",
+            UsageField::Swap => "Swap",
+            UsageField::SwapPss => "SwapPss",
+            UsageField::Locked => "Locked",
+        }
+    }
+
+    pub fn get(self, usage: &Usage) -> usize {
+        match self {
+            UsageField::Size => usage.size,
+            UsageField::KernelPageSize => usage.kernel_page_size,
+            UsageField::MmuPageSize => usage.mmu_page_size,
+            UsageField::Rss => usage.rss,
+            UsageField::Pss => usage.pss,
+            UsageField::PssDirty => usage.pss_dirty,
+            UsageField::SharedClean => usage.shared_clean,
+            UsageField::SharedDirty => usage.shared_dirty,
+            UsageField::PrivateClean => usage.private_clean,
+            UsageField::PrivateDirty => usage.private_dirty,
+            UsageField::Referenced => usage.referenced,
+            UsageField::Anonymous => usage.anonymous,
+            UsageField::Ksm => usage.ksm,
+            UsageField::LazyFree => usage.lazy_free,
+            UsageField::AnonHugePages => usage.anon_huge_pages,
+            UsageField::ShmemPmdMapped => usage.shmem_pmd_mapped,
+            UsageField::FilePmdMapped => usage.file_pmd_mapped,
+            UsageField::SharedHugetlb => usage.shared_hugetlb,
+            UsageField::PrivateHugetlb => usage.private_hugetlb,
+            UsageField::Swap => usage.swap,
+            UsageField::SwapPss => usage.swap_pss,
+            UsageField::Locked => usage.locked,
+        }
+    }
+}
+
+/// Orders usages by ascending `field`, for use with `sort_by`.
+///
+/// Wrap the arguments in [`core::cmp::Reverse`] or swap them to sort in
+/// descending order.
+pub fn by_field(field: UsageField) -> impl Fn(&Usage, &Usage) -> Ordering {
+    move |left, right| field.get(left).cmp(&field.get(right))
+}
+
+/// Pairs an item with the value it's ranked by, so items that aren't
+/// themselves `Ord` (like [`Usage`]) can go in a
+/// [`BinaryHeap`](std::collections::BinaryHeap).
+///
+/// Comparisons only look at `rank`: items with equal ranks compare equal,
+/// so the heap breaks ties arbitrarily.
+#[derive(Copy, Clone, Debug)]
+pub struct Ranked<T> {
+    pub rank: usize,
+    pub item: T,
+}
+
+impl Ranked<Usage> {
+    pub fn by(field: UsageField, usage: Usage) -> Self {
+        Ranked {
+            rank: field.get(&usage),
+            item: usage,
+        }
+    }
+}
+
+impl<T> PartialEq for Ranked<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.rank == other.rank
+    }
+}
+
+impl<T> Eq for Ranked<T> {}
+
+impl<T> PartialOrd for Ranked<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T> Ord for Ranked<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.rank.cmp(&other.rank)
+    }
+}
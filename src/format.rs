@@ -0,0 +1,21 @@
+use alloc::format;
+use alloc::string::String;
+
+/// Formats a size in bytes with IEC units and one decimal place,
+/// e.g. `512 B`, `4.0 KiB`, or `2.2 MiB`.
+pub fn format_bytes(bytes: usize) -> String {
+    const UNITS: [&str; 6] = ["KiB", "MiB", "GiB", "TiB", "PiB", "EiB"];
+
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    format!("{:.1} {}", value, UNITS[unit])
+}
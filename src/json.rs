@@ -0,0 +1,124 @@
+use std::io::Write;
+
+use serde::Serialize;
+use serde::Serializer;
+
+use crate::Device;
+use crate::Mapping;
+use crate::Permissions;
+use crate::Summary;
+use crate::Usage;
+
+/// Serialized with the same shape as [`crate::Region`], without cloning.
+#[derive(Serialize)]
+struct Region<'a, M> {
+    mapping: M,
+    usage: &'a Usage,
+}
+
+/// Writes each region as a compact JSON object on its own line,
+/// flushing after every line so consumers see records as they're written.
+pub fn write_jsonl<'a, I, W>(entries: I, mut writer: W) -> std::io::Result<()>
+where
+    I: IntoIterator<Item = &'a (Mapping, Usage)>,
+    W: Write,
+{
+    for (mapping, usage) in entries {
+        serde_json::to_writer(&mut writer, &Region { mapping, usage })?;
+        writer.write_all(b"\n")?;
+        writer.flush()?;
+    }
+    Ok(())
+}
+
+/// Renders a whole process as one JSON document:
+///
+/// ```json
+/// {
+///   "regions": [{ "mapping": { "start": "0x400000", ... }, "usage": { ... } }, ...],
+///   "summary": { "count": 2, "size": 4096, "rss": 4096, ... }
+/// }
+/// ```
+///
+/// Unlike [`write_jsonl`], addresses and offsets are `0x`-prefixed hex
+/// strings and [`Mapping::raw`] is omitted. Sizes are in bytes.
+pub fn to_json(entries: &[(Mapping, Usage)]) -> String {
+    let regions = entries
+        .iter()
+        .map(|(mapping, usage)| Region {
+            mapping: HexMapping::from(mapping),
+            usage,
+        })
+        .collect::<Vec<_>>();
+
+    let mut summary = Summary::default();
+    for (mapping, usage) in entries {
+        summary.add(mapping, usage);
+    }
+    let document = Document {
+        regions,
+        summary: Totals {
+            count: summary.count,
+            size: summary.size,
+            rss: summary.rss,
+            pss: summary.pss,
+            uss: summary.uss,
+            swap: summary.swap,
+        },
+    };
+
+    serde_json::to_string(&document).expect("Serializing to a string can't fail")
+}
+
+#[derive(Serialize)]
+struct Document<'a> {
+    regions: Vec<Region<'a, HexMapping<'a>>>,
+    summary: Totals,
+}
+
+/// [`Mapping`] with addresses and offsets as hex strings.
+#[derive(Serialize)]
+struct HexMapping<'a> {
+    start: Hex,
+    end: Hex,
+    permissions: Permissions,
+    offset: Hex,
+    device: Device,
+    inode: usize,
+    path: Option<&'a str>,
+    deleted: bool,
+}
+
+impl<'a> From<&'a Mapping> for HexMapping<'a> {
+    fn from(mapping: &'a Mapping) -> Self {
+        HexMapping {
+            start: Hex(mapping.start),
+            end: Hex(mapping.end),
+            permissions: mapping.permissions,
+            offset: Hex(mapping.offset),
+            device: mapping.device,
+            inode: mapping.inode,
+            path: mapping.path.as_deref(),
+            deleted: mapping.deleted,
+        }
+    }
+}
+
+/// The totals of a [`Summary`], without the per-kind breakdown.
+#[derive(Serialize)]
+struct Totals {
+    count: usize,
+    size: usize,
+    rss: usize,
+    pss: usize,
+    uss: usize,
+    swap: usize,
+}
+
+struct Hex(u64);
+
+impl Serialize for Hex {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&format_args!("{:#x}", self.0))
+    }
+}
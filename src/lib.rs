@@ -1,21 +1,140 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
 use bitflags::bitflags;
 
+mod category;
+#[cfg(feature = "std")]
+mod clear_refs;
+#[cfg(feature = "csv")]
+mod csv;
+mod delta;
+mod diff;
+mod display;
+#[cfg(feature = "std")]
+mod entries;
+mod error;
+mod field;
+mod format;
+#[cfg(feature = "serde")]
+mod json;
+mod line;
+mod mapping;
+#[cfg(feature = "std")]
 mod parse;
+mod permissions;
+#[cfg(feature = "procfs")]
+mod procfs;
+#[cfg(feature = "std")]
+mod read;
+mod region;
+#[cfg(feature = "serde")]
+mod serialize;
+mod snapshot;
+#[cfg(feature = "tokio")]
+mod stream;
+mod summary;
+#[cfg(feature = "libc")]
+mod sys;
+mod table;
+#[cfg(feature = "std")]
+mod top;
+mod usage;
+mod vm_flags;
 
+pub use category::categorize;
+pub use category::Category;
+#[cfg(feature = "std")]
+pub use clear_refs::reset_references;
+#[cfg(feature = "std")]
+pub use clear_refs::reset_soft_dirty;
+#[cfg(feature = "csv")]
+pub use csv::write_csv;
+pub use delta::UsageDelta;
+pub use diff::changes;
+pub use diff::diff;
+pub use diff::Change;
+pub use diff::DiffDisplay;
+pub use diff::DiffReport;
+pub use diff::MatchBy;
+#[cfg(feature = "std")]
+pub use entries::Entries;
+#[cfg(feature = "std")]
+pub use entries::Mappings;
+pub use error::SmapsError;
+pub use field::by_field;
+pub use field::Ranked;
+pub use field::UsageField;
+pub use format::format_bytes;
+#[cfg(feature = "serde")]
+pub use json::to_json;
+#[cfg(feature = "serde")]
+pub use json::write_jsonl;
+pub use mapping::by_pss;
+pub use mapping::by_rss;
+pub use mapping::DEFAULT_PAGE_SIZE;
+#[cfg(feature = "std")]
 pub use parse::Parser;
+#[cfg(feature = "std")]
+pub use read::read;
+#[cfg(feature = "rayon")]
+pub use read::read_all;
+#[cfg(feature = "std")]
+pub use read::read_maps;
+#[cfg(feature = "std")]
+pub use read::read_rollup;
+#[cfg(feature = "std")]
+pub use read::read_self;
+pub use region::Region;
+pub use region::RegionKind;
+pub use snapshot::Snapshot;
+#[cfg(feature = "tokio")]
+pub use stream::EntryStream;
+#[cfg(feature = "std")]
+pub use summary::summarize;
+#[cfg(feature = "std")]
+pub use summary::summarize_pid;
+pub use summary::Summary;
+pub use table::Table;
+#[cfg(feature = "std")]
+pub use top::top_paths;
+pub use usage::DominantKind;
+pub use usage::Inconsistency;
+pub use usage::RssBreakdown;
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Mapping {
-    pub start: usize,
-    pub end: usize,
+    /// Addresses and offsets are `u64` regardless of the target, so that
+    /// smaps captured on a 64-bit host can be parsed on a 32-bit one.
+    pub start: u64,
+    pub end: u64,
     pub permissions: Permissions,
-    pub offset: usize,
+    pub offset: u64,
     pub device: Device,
     pub inode: usize,
+    /// backing file or pseudo-path, without any ` (deleted)` suffix
     pub path: Option<String>,
+    /// the backing file was unlinked, which the kernel marks by appending
+    /// ` (deleted)` to the path
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub deleted: bool,
+    /// original header line, retained by [`Parser::keep_raw`]
+    pub raw: Option<String>,
 }
 
-#[derive(Clone, Debug, Default, PartialEq, Eq)]
+/// Memory usage of a single mapping.
+///
+/// Sizes are in **bytes**, not the kB printed by the kernel: values are
+/// scaled by their unit while parsing, so divide by 1024 to recover the
+/// numbers shown in `/proc/pid/smaps`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct Usage {
     pub size: usize,
     pub kernel_page_size: usize,
@@ -40,13 +159,26 @@ pub struct Usage {
     pub swap: usize,
     pub swap_pss: usize,
     pub locked: usize,
-    pub thp_eligible: bool,
+    /// `None` if the kernel doesn't report `THPeligible`
+    pub thp_eligible: Option<bool>,
+    /// `None` if the kernel doesn't report `ProtectionKey`; see
+    /// [`Usage::protection_key`]
     pub protection_key: Option<usize>,
-    pub vm_flags: VmFlags,
+    /// set by [`Usage::merge`] once a total combines mappings with different
+    /// protection keys, which leaves `protection_key` as `None` for good
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "core::ops::Not::not"))]
+    pub mixed_protection_keys: bool,
+    /// `None` if the kernel doesn't report `VmFlags` (before Linux 3.8),
+    /// as opposed to reporting an empty set
+    pub vm_flags: Option<VmFlags>,
+    /// `VmFlags` tokens this crate doesn't recognize, in the order they
+    /// appeared, e.g. flags added by a newer kernel
+    pub unknown_vm_flags: Vec<String>,
 }
 
 bitflags! {
-    #[derive(Copy, Clone, Debug, PartialEq, Eq)]
+    /// Serialized as in the smaps header, e.g. `"r-xp"`.
+    #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
     pub struct Permissions: u8 {
         const X = 1 << 0;
         const W = 1 << 1;
@@ -56,14 +188,16 @@ bitflags! {
     }
 }
 
-#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Device {
     pub major: u32,
     pub minor: u32,
 }
 
 bitflags! {
-    #[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+    /// Serialized as in the `VmFlags` line, e.g. `"rd ex mr mw me"`.
+    #[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
     pub struct VmFlags: u32 {
         /// readable
         const RD = 1 << 0;
@@ -109,7 +243,7 @@ bitflags! {
         const SF = 1 << 20;
         /// non-linear mapping (removed in Linux 4.0)
         const NL = 1 << 21;
-        /// architecture specific flag
+        /// architecture specific flag (`VM_ARCH_1`), see [`VmFlags::is_arch_specific`]
         const AR = 1 << 22;
         /// wipe on fork (since Linux 4.14)
         const WF = 1 << 23;
@@ -0,0 +1,368 @@
+use alloc::borrow::ToOwned;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::num::IntErrorKind;
+use core::str::FromStr;
+
+use crate::Device;
+use crate::Mapping;
+use crate::Permissions;
+use crate::SmapsError;
+use crate::Usage;
+use crate::VmFlags;
+
+impl Usage {
+    /// Parses one line of a usage block into `self`, for parsing without
+    /// [`crate::Parser`] (e.g. without `std`).
+    ///
+    /// Returns `false` if `line` isn't a `Key: value [unit]` pair, such as
+    /// the next mapping header (any line with a `-`, like the address
+    /// range). Unknown keys and VM flags are ignored unless `strict`.
+    pub fn parse_line(&mut self, line: &str, strict: bool) -> Result<bool, SmapsError> {
+        if line.contains('-') {
+            return Ok(false);
+        }
+
+        if line.starts_with("VmFlags") {
+            self.vm_flags = Some(VmFlags::parse(
+                line.trim_start_matches("VmFlags:").trim_ascii_start(),
+                strict,
+                &mut self.unknown_vm_flags,
+            )?);
+            return Ok(true);
+        }
+
+        if let Some(key) = line.strip_prefix("ProtectionKey:") {
+            self.protection_key = Some(Self::parse_protection_key(line, key.trim_ascii())?);
+            return Ok(true);
+        }
+
+        let Some((key, value)) = Self::split_line(line)? else {
+            return Ok(false);
+        };
+
+        match key {
+            "Size" => self.size = value,
+            "KernelPageSize" => self.kernel_page_size = value,
+            "MMUPageSize" => self.mmu_page_size = value,
+            "Rss" => self.rss = value,
+            "Pss" => self.pss = value,
+            "Pss_Dirty" => self.pss_dirty = value,
+            "Shared_Clean" => self.shared_clean = value,
+            "Shared_Dirty" => self.shared_dirty = value,
+            "Private_Clean" => self.private_clean = value,
+            "Private_Dirty" => self.private_dirty = value,
+            "Referenced" => self.referenced = value,
+            "Anonymous" => self.anonymous = value,
+            "KSM" => self.ksm = value,
+            "LazyFree" => self.lazy_free = value,
+            "AnonHugePages" => self.anon_huge_pages = value,
+            "ShmemPmdMapped" => self.shmem_pmd_mapped = value,
+            "FilePmdMapped" => self.file_pmd_mapped = value,
+            "Shared_Hugetlb" => self.shared_hugetlb = value,
+            "Private_Hugetlb" => self.private_hugetlb = value,
+            "Swap" => self.swap = value,
+            "SwapPss" => self.swap_pss = value,
+            "Locked" => self.locked = value,
+            "THPeligible" => self.thp_eligible = Some(value != 0),
+            key if strict => return Err(SmapsError::UnknownKey(key.to_owned())),
+            _ => {}
+        }
+
+        Ok(true)
+    }
+
+    /// Accepts the kernel's decimal form, or hex with a `0x` prefix, and
+    /// rejects keys outside of `0..=MAX_PROTECTION_KEY`.
+    fn parse_protection_key(line: &str, data: &str) -> Result<usize, SmapsError> {
+        let key = if data.starts_with("0x") || data.starts_with("0X") {
+            hex_digits(data).and_then(|digits| usize::from_str_radix(digits, 16).ok())
+        } else {
+            data.parse().ok()
+        };
+        key.filter(|key| *key <= Usage::MAX_PROTECTION_KEY)
+            .ok_or_else(|| SmapsError::InvalidField {
+                field: "ProtectionKey",
+                line: line.to_owned(),
+            })
+    }
+
+    fn split_line(line: &str) -> Result<Option<(&str, usize)>, SmapsError> {
+        let mut iter = line.split_ascii_whitespace();
+        let (Some(key), Some(value)) = (iter.next(), iter.next()) else {
+            return Ok(None);
+        };
+        let key = key.trim_end_matches(":");
+        // The kernel's `kB` is binary, so the variants emitted by
+        // smaps-compatible tools (`KB`, `KiB`, ...) are treated as binary too.
+        const UNITS: [(&str, &str, u32); 5] = [
+            ("b", "b", 0),
+            ("kb", "kib", 10),
+            ("mb", "mib", 20),
+            ("gb", "gib", 30),
+            ("tb", "tib", 40),
+        ];
+        let unit = match iter.next() {
+            None => 0,
+            Some(unit) => UNITS
+                .iter()
+                .find(|(short, long, _)| {
+                    unit.eq_ignore_ascii_case(short) || unit.eq_ignore_ascii_case(long)
+                })
+                .map(|(_, _, shift)| *shift)
+                .ok_or_else(|| SmapsError::UnknownUnit(unit.to_owned()))?,
+        };
+
+        let overflow = || SmapsError::Overflow(line.trim_ascii().to_owned());
+        match (iter.next(), value.parse::<usize>()) {
+            (None, Ok(value)) => value
+                .checked_shl(unit)
+                .filter(|shifted| shifted >> unit == value)
+                .map(|shifted| Some((key, shifted)))
+                .ok_or_else(overflow),
+            (None, Err(error)) if *error.kind() == IntErrorKind::PosOverflow => Err(overflow()),
+            _ => Ok(None),
+        }
+    }
+}
+
+impl VmFlags {
+    /// Collects unrecognized flags into `unknown`, unless `strict`.
+    fn parse(data: &str, strict: bool, unknown: &mut Vec<String>) -> Result<Self, SmapsError> {
+        data.split_ascii_whitespace()
+            .try_fold(VmFlags::empty(), |flags, flag| {
+                let flag = match flag {
+                    "rd" => Self::RD,
+                    "wr" => Self::WR,
+                    "ex" => Self::EX,
+                    "sh" => Self::SH,
+                    "mr" => Self::MR,
+                    "mw" => Self::MW,
+                    "me" => Self::ME,
+                    "ms" => Self::MS,
+                    "gd" => Self::GD,
+                    "pf" => Self::PF,
+                    "dw" => Self::DW,
+                    "lo" => Self::LO,
+                    "io" => Self::IO,
+                    "sr" => Self::SR,
+                    "rr" => Self::RR,
+                    "dc" => Self::DC,
+                    "de" => Self::DE,
+                    "ac" => Self::AC,
+                    "nr" => Self::NR,
+                    "ht" => Self::HT,
+                    "sf" => Self::SF,
+                    "nl" => Self::NL,
+                    "ar" => Self::AR,
+                    "wf" => Self::WF,
+                    "dd" => Self::DD,
+                    "sd" => Self::SD,
+                    "mm" => Self::MM,
+                    "hg" => Self::HG,
+                    "nh" => Self::NH,
+                    "mg" => Self::MG,
+                    "um" => Self::UM,
+                    "uw" => Self::UW,
+                    flag if strict => return Err(SmapsError::UnknownVmFlag(flag.to_owned())),
+                    flag => {
+                        unknown.push(flag.to_owned());
+                        VmFlags::empty()
+                    }
+                };
+                Ok(flags | flag)
+            })
+    }
+}
+
+impl Mapping {
+    pub(crate) fn parse(line: &str, strict: bool) -> Result<Option<Self>, SmapsError> {
+        let mut rest = line;
+        let mut next = || {
+            let trimmed = rest.trim_ascii_start();
+            let (field, tail) = trimmed.split_at(
+                trimmed
+                    .find(|char: char| char.is_ascii_whitespace())
+                    .unwrap_or(trimmed.len()),
+            );
+            rest = tail;
+            Some(field).filter(|field| !field.is_empty())
+        };
+        let Some((start, end)) = next().and_then(|range| range.split_once('-')) else {
+            return Ok(None);
+        };
+        let (Some(start), Some(end)) = (parse_hex(start)?, parse_hex(end)?) else {
+            return Ok(None);
+        };
+        // Once the address range is valid, the line is a header, so report
+        // missing or bad fields rather than ending the parse
+        let missing = |field| SmapsError::MissingField {
+            field,
+            line: line.to_owned(),
+        };
+        let invalid = |field| SmapsError::InvalidField {
+            field,
+            line: line.to_owned(),
+        };
+
+        // An inverted range would make `size` underflow
+        if end < start {
+            return Err(invalid("end"));
+        }
+
+        let permissions =
+            Permissions::parse(next().ok_or_else(|| missing("permissions"))?, strict)?;
+        let offset = next().ok_or_else(|| missing("offset"))?;
+        let device = next().ok_or_else(|| missing("device"))?;
+        let inode = next().ok_or_else(|| missing("inode"))?;
+
+        let offset = parse_hex(offset)?.ok_or_else(|| invalid("offset"))?;
+        let device = Device::parse(device).ok_or_else(|| invalid("device"))?;
+        let inode = inode.parse().map_err(|_| invalid("inode"))?;
+
+        // The kernel pads the header with spaces up to `PATH_COLUMN` (on
+        // 64-bit) and then writes one more before the path, so whitespace
+        // past that point belongs to the path. Otherwise, the path starts
+        // after the run of whitespace following the inode.
+        let separator = line.len() - rest.len();
+        let padding = line.len() - rest.trim_ascii_start().len();
+        let path = line[padding.min((separator + 1).max(PATH_COLUMN))..].trim_ascii_end();
+        let (path, deleted) = match path.strip_suffix(" (deleted)") {
+            Some(path) => (path, true),
+            None => (path, false),
+        };
+        let path = Some(path).filter(|path| !path.is_empty());
+
+        Ok(Some(Self {
+            start,
+            end,
+            permissions,
+            offset,
+            device,
+            inode,
+            path: path.map(str::to_owned),
+            deleted,
+            raw: None,
+        }))
+    }
+}
+
+impl Permissions {
+    /// Accepts either case, since some tools emit uppercase permissions.
+    ///
+    /// Only the first four characters are interpreted. Any more are
+    /// rejected in strict mode and otherwise ignored, in case a future
+    /// kernel extends the field.
+    fn parse(data: &str, strict: bool) -> Result<Self, SmapsError> {
+        let invalid = |position| SmapsError::InvalidPermissions {
+            permissions: data.to_owned(),
+            position,
+        };
+
+        let bytes = data.as_bytes();
+        if strict && bytes.len() > 4 {
+            return Err(invalid(4));
+        }
+        let Some(bytes) = bytes.first_chunk::<4>() else {
+            return Err(invalid(bytes.len()));
+        };
+        let bytes = bytes.map(|byte| byte.to_ascii_lowercase());
+
+        let read = match bytes[0] {
+            b'-' => Permissions::empty(),
+            b'r' => Permissions::R,
+            _ => return Err(invalid(0)),
+        };
+
+        let write = match bytes[1] {
+            b'-' => Permissions::empty(),
+            b'w' => Permissions::W,
+            _ => return Err(invalid(1)),
+        };
+
+        let execute = match bytes[2] {
+            b'-' => Permissions::empty(),
+            b'x' => Permissions::X,
+            _ => return Err(invalid(2)),
+        };
+
+        let shared = match bytes[3] {
+            b's' => Permissions::S,
+            b'p' => Permissions::P,
+            _ => return Err(invalid(3)),
+        };
+
+        Ok(read | write | execute | shared)
+    }
+}
+
+impl FromStr for Mapping {
+    type Err = SmapsError;
+
+    /// Parses a single header line, e.g. as formatted by `Display`.
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        Mapping::parse(line, true)?.ok_or_else(|| SmapsError::Malformed {
+            line_number: 1,
+            line: line.to_owned(),
+        })
+    }
+}
+
+impl FromStr for Permissions {
+    type Err = SmapsError;
+
+    /// Parses exactly four characters, e.g. `r-xp`, as the inverse of
+    /// `Display`.
+    fn from_str(data: &str) -> Result<Self, Self::Err> {
+        Permissions::parse(data, true)
+    }
+}
+
+impl FromStr for VmFlags {
+    type Err = SmapsError;
+
+    /// Parses space-separated two-letter tokens, e.g. `rd ex mr`, as the
+    /// inverse of `Display`. Unrecognized tokens are rejected.
+    fn from_str(data: &str) -> Result<Self, Self::Err> {
+        VmFlags::parse(data, true, &mut Vec::new())
+    }
+}
+
+impl Device {
+    fn parse(data: &str) -> Option<Self> {
+        let (major, minor) = data.split_once(':')?;
+        Some(Self {
+            major: u32::from_str_radix(hex_digits(major)?, 16).ok()?,
+            minor: u32::from_str_radix(hex_digits(minor)?, 16).ok()?,
+        })
+    }
+}
+
+/// Column where the kernel starts a mapping's path on 64-bit systems:
+/// `25 + 6 * sizeof(void *) - 1` bytes of header and padding, then a space.
+pub(crate) const PATH_COLUMN: usize = 73;
+
+/// Distinguishes values too large for `u64` from malformed ones, so they
+/// aren't silently dropped.
+fn parse_hex(data: &str) -> Result<Option<u64>, SmapsError> {
+    let Some(digits) = hex_digits(data) else {
+        return Ok(None);
+    };
+    match u64::from_str_radix(digits, 16) {
+        Ok(value) => Ok(Some(value)),
+        Err(error) if *error.kind() == IntErrorKind::PosOverflow => {
+            Err(SmapsError::Overflow(data.to_owned()))
+        }
+        Err(_) => Ok(None),
+    }
+}
+
+/// Strips an optional `0x` or `0X` prefix, as emitted by some tools, and
+/// checks that only hex digits remain (`from_str_radix` also accepts a sign).
+fn hex_digits(data: &str) -> Option<&str> {
+    let digits = data
+        .strip_prefix("0x")
+        .or_else(|| data.strip_prefix("0X"))
+        .unwrap_or(data);
+    Some(digits).filter(|digits| digits.bytes().all(|byte| byte.is_ascii_hexdigit()))
+}
@@ -0,0 +1,151 @@
+use core::cmp::Ordering;
+#[cfg(all(feature = "std", unix))]
+use std::os::unix::fs::MetadataExt;
+#[cfg(all(feature = "std", unix))]
+use std::path::Path;
+
+use crate::Device;
+use crate::Mapping;
+use crate::Usage;
+
+/// Page size assumed when a [`Usage`] has no `KernelPageSize`.
+pub const DEFAULT_PAGE_SIZE: usize = 4096;
+
+impl Mapping {
+    /// Returns the final component of the path. Pseudo-paths like `[heap]`
+    /// are returned as-is.
+    pub fn basename(&self) -> Option<&str> {
+        let path = self.path.as_deref()?;
+        if path.starts_with('[') {
+            return Some(path);
+        }
+
+        path.trim_end_matches('/')
+            .rsplit('/')
+            .next()
+            .filter(|name| !name.is_empty())
+    }
+
+    /// The `(start, end)` address range that [`Ord`] compares first, for
+    /// sorting or binary searching by address alone, e.g. with
+    /// `sort_by_key` or `partition_point`.
+    pub fn sort_key(&self) -> (u64, u64) {
+        (self.start, self.end)
+    }
+
+    /// Length of the address range in bytes.
+    pub fn size(&self) -> u64 {
+        self.end - self.start
+    }
+
+    /// Checks whether `address` falls within `[start, end)`.
+    pub fn contains(&self, address: u64) -> bool {
+        self.start <= address && address < self.end
+    }
+
+    /// Translates `address` to an offset into the backing file, for
+    /// symbolizing, or `None` if it's outside this mapping or the offset
+    /// would overflow `u64`.
+    pub fn offset_of(&self, address: u64) -> Option<u64> {
+        if !self.contains(address) {
+            return None;
+        }
+        self.offset.checked_add(address - self.start)
+    }
+
+    /// Checks that both `start` and `end` are multiples of `page_size`.
+    ///
+    /// A `page_size` of 0 is never valid, so nothing is aligned to it.
+    pub fn is_page_aligned(&self, page_size: usize) -> bool {
+        let page_size = page_size as u64;
+        page_size != 0 && self.start % page_size == 0 && self.end % page_size == 0
+    }
+
+    /// Checks alignment against the `kernel_page_size` reported in `usage`,
+    /// or [`DEFAULT_PAGE_SIZE`] if it wasn't reported.
+    pub fn is_page_aligned_for(&self, usage: &Usage) -> bool {
+        self.is_page_aligned(page_size(usage))
+    }
+}
+
+/// `kernel_page_size` from `usage`, or [`DEFAULT_PAGE_SIZE`] if it wasn't reported.
+pub(crate) fn page_size(usage: &Usage) -> usize {
+    match usage.kernel_page_size {
+        0 => DEFAULT_PAGE_SIZE,
+        page_size => page_size,
+    }
+}
+
+impl Ord for Mapping {
+    /// Orders by address range (`start`, then `end`). The remaining fields
+    /// only break ties, so that the ordering agrees with `Eq`: permissions
+    /// bits, offset, device, inode, path (anonymous first), `deleted`, and
+    /// finally `raw`.
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.start
+            .cmp(&other.start)
+            .then(self.end.cmp(&other.end))
+            .then(self.permissions.bits().cmp(&other.permissions.bits()))
+            .then(self.offset.cmp(&other.offset))
+            .then(self.device.major.cmp(&other.device.major))
+            .then(self.device.minor.cmp(&other.device.minor))
+            .then(self.inode.cmp(&other.inode))
+            .then_with(|| self.path.cmp(&other.path))
+            .then(self.deleted.cmp(&other.deleted))
+            .then_with(|| self.raw.cmp(&other.raw))
+    }
+}
+
+impl PartialOrd for Mapping {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Orders regions by descending `pss`, for use with `sort_by`.
+pub fn by_pss(left: &(Mapping, Usage), right: &(Mapping, Usage)) -> Ordering {
+    right.1.pss.cmp(&left.1.pss)
+}
+
+/// Orders regions by descending `rss`, for use with `sort_by`.
+pub fn by_rss(left: &(Mapping, Usage), right: &(Mapping, Usage)) -> Ordering {
+    right.1.rss.cmp(&left.1.rss)
+}
+
+impl Device {
+    pub fn new(major: u32, minor: u32) -> Self {
+        Device { major, minor }
+    }
+
+    /// Unpacks a `dev_t`, e.g. `st_dev` from `stat`, using the Linux
+    /// `makedev` encoding.
+    pub fn from_dev_t(device: u64) -> Self {
+        Device {
+            major: (((device >> 32) & 0xffff_f000) | ((device >> 8) & 0xfff)) as u32,
+            minor: (((device >> 12) & 0xffff_ff00) | (device & 0xff)) as u32,
+        }
+    }
+
+    /// Packs into a `dev_t` using the Linux `makedev` encoding, for
+    /// comparison against `st_dev` from `stat`.
+    pub fn to_dev_t(&self) -> u64 {
+        let (major, minor) = (u64::from(self.major), u64::from(self.minor));
+        ((major & 0xffff_f000) << 32)
+            | ((major & 0xfff) << 8)
+            | ((minor & 0xffff_ff00) << 12)
+            | (minor & 0xff)
+    }
+
+    /// Returns the device containing `path`, for comparison against
+    /// [`crate::Mapping::device`].
+    #[cfg(all(feature = "std", unix))]
+    pub fn of_path(path: &Path) -> std::io::Result<Self> {
+        Ok(Device::from_dev_t(std::fs::metadata(path)?.dev()))
+    }
+
+    /// Checks for device `00:00`, used by anonymous mappings, pseudo-paths
+    /// like `[heap]`, and the `[rollup]` entry of `smaps_rollup`.
+    pub fn is_anonymous(&self) -> bool {
+        self.major == 0 && self.minor == 0
+    }
+}
@@ -1,258 +1,294 @@
-use core::iter;
-use core::iter::Peekable;
-use core::ops::BitOr;
 use std::fs::File;
 use std::io::BufRead;
 use std::io::BufReader;
+use std::io::Cursor;
+use std::io::StdinLock;
 use std::path::Path;
 
-use crate::Device;
 use crate::Mapping;
-use crate::Permissions;
+use crate::Mappings;
+use crate::SmapsError;
 use crate::Usage;
-use crate::VmFlags;
 
+/// Parses smaps output one mapping header and usage block at a time.
+///
+/// Fields come and go across kernel versions, so any usage key missing from
+/// a block keeps its default, and any key or VM flag this crate doesn't know
+/// about is ignored. Use [`Parser::strict`] to reject the latter instead.
 pub struct Parser<R: BufRead, S> {
-    iter: iter::Peekable<std::io::Lines<R>>,
+    lines: Lines<R>,
+    options: Options,
+    mappings: usize,
     _state: S,
 }
 
+#[derive(Copy, Clone, Debug, Default)]
+struct Options {
+    raw: bool,
+    strict: bool,
+    tolerant: bool,
+    limit: Option<usize>,
+}
+
 pub struct ParseMapping;
 pub struct ParseUsage;
 
-impl Parser<BufReader<File>, ParseMapping> {
-    pub fn open(path: &Path) -> std::io::Result<Self> {
-        File::open(path)
-            .map(BufReader::new)
-            .map(BufReader::lines)
-            .map(Iterator::peekable)
-            .map(|iter| Self {
-                iter,
-                _state: ParseMapping,
-            })
-    }
+/// Like [`BufRead::lines`] with one line of lookahead, but reusing a single
+/// buffer instead of allocating a `String` per line.
+struct Lines<R> {
+    reader: R,
+    line: String,
+    /// `line` holds a line that hasn't been consumed yet
+    peeked: bool,
+    /// number of lines read so far, i.e. `line`'s line number
+    number: usize,
 }
 
-impl<R: BufRead> Parser<R, ParseMapping> {
-    pub fn next(mut self) -> std::io::Result<(Parser<R, ParseUsage>, Option<Mapping>)> {
-        let mapping = self
-            .iter
-            .next()
-            .transpose()?
-            .as_deref()
-            .and_then(Mapping::parse);
+impl<R: BufRead> Lines<R> {
+    fn peek(&mut self) -> std::io::Result<Option<&str>> {
+        if !self.peeked {
+            self.line.clear();
+            if self.reader.read_line(&mut self.line)? == 0 {
+                return Ok(None);
+            }
+            let len = self.line.trim_end_matches(['\n', '\r']).len();
+            self.line.truncate(len);
+            self.peeked = true;
+            self.number += 1;
+        }
 
-        Ok((self.with_state(ParseUsage), mapping))
+        Ok(Some(&self.line))
     }
-}
 
-impl<R: BufRead> Parser<R, ParseUsage> {
-    pub fn next(mut self) -> std::io::Result<(Parser<R, ParseMapping>, Option<Usage>)> {
-        let usage = Usage::parse(&mut self.iter)?;
-        Ok((self.with_state(ParseMapping), usage))
+    fn next(&mut self) -> std::io::Result<Option<&str>> {
+        self.next_if(|_| true)
     }
 
-    pub fn skip(mut self) -> Parser<R, ParseMapping> {
-        while self
-            .iter
-            .peek()
-            .map(Result::as_ref)
-            .is_some_and(|line| line.is_ok_and(|line| !line.contains('-')))
-        {
-            self.iter.next();
+    fn next_if(&mut self, predicate: impl Fn(&str) -> bool) -> std::io::Result<Option<&str>> {
+        match self.peek()? {
+            Some(line) if predicate(line) => {
+                self.peeked = false;
+                Ok(Some(&self.line))
+            }
+            _ => Ok(None),
         }
-
-        self.with_state(ParseMapping)
     }
-}
 
-impl<R: BufRead, S> Parser<R, S> {
-    fn with_state<T>(self, state: T) -> Parser<R, T> {
-        Parser {
-            iter: self.iter,
-            _state: state,
+    /// Reports the most recently read line as malformed.
+    fn malformed(&self) -> SmapsError {
+        SmapsError::Malformed {
+            line_number: self.number,
+            line: self.line.clone(),
         }
     }
 }
 
-impl Usage {
-    fn parse(
-        iter: &mut Peekable<impl Iterator<Item = std::io::Result<String>>>,
-    ) -> std::io::Result<Option<Self>> {
-        let mut usage = Self::default();
-
-        while let Some(line) =
-            iter.next_if(|line| line.as_ref().is_ok_and(|line| !line.contains('-')))
-        {
-            let line = line?;
+impl Parser<BufReader<File>, ParseMapping> {
+    pub fn open(path: &Path) -> std::io::Result<Self> {
+        File::open(path).map(Self::from_file)
+    }
 
-            if line.starts_with("VmFlags") {
-                usage.vm_flags =
-                    VmFlags::parse(line.trim_start_matches("VmFlags:").trim_ascii_start());
-                continue;
-            }
+    /// Opens a `maps` file, e.g. `/proc/<pid>/maps`, which has the same
+    /// header lines as smaps but no usage blocks.
+    pub fn open_maps(path: &Path) -> std::io::Result<Mappings<BufReader<File>>> {
+        Self::open(path).map(Self::mappings_only)
+    }
 
-            let Some((key, value)) = Self::parse_line(&line) else {
-                return Ok(None);
-            };
-
-            match key {
-                "Size" => usage.size = value,
-                "KernelPageSize" => usage.kernel_page_size = value,
-                "MMUPageSize" => usage.mmu_page_size = value,
-                "Rss" => usage.rss = value,
-                "Pss" => usage.pss = value,
-                "Pss_Dirty" => usage.pss_dirty = value,
-                "Shared_Clean" => usage.shared_clean = value,
-                "Shared_Dirty" => usage.shared_dirty = value,
-                "Private_Clean" => usage.private_clean = value,
-                "Private_Dirty" => usage.private_dirty = value,
-                "Referenced" => usage.referenced = value,
-                "Anonymous" => usage.anonymous = value,
-                "KSM" => usage.ksm = value,
-                "LazyFree" => usage.lazy_free = value,
-                "AnonHugePages" => usage.anon_huge_pages = value,
-                "ShmemPmdMapped" => usage.shmem_pmd_mapped = value,
-                "FilePmdMapped" => usage.file_pmd_mapped = value,
-                "Shared_Hugetlb" => usage.shared_hugetlb = value,
-                "Private_Hugetlb" => usage.private_hugetlb = value,
-                "Swap" => usage.swap = value,
-                "SwapPss" => usage.swap_pss = value,
-                "Locked" => usage.locked = value,
-                "THPeligible" => usage.thp_eligible = value != 0,
-                "ProtectionKey" => usage.protection_key = Some(value),
-                key => panic!("Unrecognized key: {}", key),
-            }
-        }
+    /// Parses from an already-open file, e.g. a descriptor for
+    /// `/proc/<pid>/smaps` handed over by a more privileged helper
+    /// (see [`std::os::fd::FromRawFd`]).
+    pub fn from_file(file: File) -> Self {
+        Self::new(BufReader::new(file))
+    }
 
-        Ok(Some(usage))
+    /// Opens `/proc/<pid>/smaps`, reporting a missing process as
+    /// [`SmapsError::ProcessGone`] and an unreadable one as
+    /// [`SmapsError::PermissionDenied`].
+    pub fn from_pid(pid: u32) -> Result<Self, SmapsError> {
+        Self::from_proc(pid, "smaps")
     }
 
-    fn parse_line(line: &str) -> Option<(&str, usize)> {
-        let mut iter = line.split_ascii_whitespace();
-        let key = iter.next()?.trim_end_matches(":");
-        let value = iter.next()?;
-        let unit = match iter.next() {
-            Some("kB") => 10,
-            Some("mB") => 20,
-            Some("gB") => 30,
-            Some("tB") => 40,
-            Some(unit) => panic!("Unrecognized unit: {}", unit),
-            None => 0,
-        };
+    /// Opens `/proc/<pid>/<file>`, with the same error mapping as
+    /// [`Parser::from_pid`].
+    pub(crate) fn from_proc(pid: u32, file: &str) -> Result<Self, SmapsError> {
+        let path = format!("/proc/{}/{}", pid, file);
+        Self::open(Path::new(&path)).map_err(SmapsError::from_proc_io)
+    }
+}
 
-        match iter.next() {
-            Some(_) => None,
-            None => Some((key, value.parse::<usize>().ok()? << unit)),
-        }
+impl Parser<Cursor<Vec<u8>>, ParseMapping> {
+    /// Reads the whole file up front and parses from memory.
+    ///
+    /// The kernel generates `/proc` files incrementally as they're read, so
+    /// reading in one go narrows (but doesn't close) the window in which
+    /// concurrent changes to the address space can tear the output.
+    pub fn snapshot(path: &Path) -> std::io::Result<Self> {
+        std::fs::read(path).map(Cursor::new).map(Self::new)
     }
 }
 
-impl VmFlags {
-    fn parse(data: &str) -> Self {
-        data.split_ascii_whitespace()
-            .map(|flag| match flag {
-                "rd" => Self::RD,
-                "wr" => Self::WR,
-                "ex" => Self::EX,
-                "sh" => Self::SH,
-                "mr" => Self::MR,
-                "mw" => Self::MW,
-                "me" => Self::ME,
-                "ms" => Self::MS,
-                "gd" => Self::GD,
-                "pf" => Self::PF,
-                "dw" => Self::DW,
-                "lo" => Self::LO,
-                "io" => Self::IO,
-                "sr" => Self::SR,
-                "rr" => Self::RR,
-                "dc" => Self::DC,
-                "de" => Self::DE,
-                "ac" => Self::AC,
-                "nr" => Self::NR,
-                "ht" => Self::HT,
-                "sf" => Self::SF,
-                "nl" => Self::NL,
-                "ar" => Self::AR,
-                "wf" => Self::WF,
-                "dd" => Self::DD,
-                "sd" => Self::SD,
-                "mm" => Self::MM,
-                "hg" => Self::HG,
-                "nh" => Self::NH,
-                "mg" => Self::MG,
-                "um" => Self::UM,
-                "uw" => Self::UW,
-                flag => panic!("Unrecognized VM flag: {}", flag),
-            })
-            .fold(VmFlags::empty(), BitOr::bitor)
+impl<'a> Parser<&'a [u8], ParseMapping> {
+    /// Parses smaps output already in memory, e.g. received over a socket.
+    /// Use [`str::as_bytes`] to parse from a string.
+    pub fn from_bytes(bytes: &'a [u8]) -> Self {
+        Self::new(bytes)
     }
 }
 
-impl Mapping {
-    fn parse(line: &str) -> Option<Self> {
-        let mut iter = line.split_ascii_whitespace();
-        let (start, end) = iter.next().unwrap().split_once('-')?;
-        let permissions = iter.next().and_then(Permissions::parse)?;
-        let offset = iter.next()?;
-        let device = iter.next().and_then(Device::parse)?;
-        let inode = iter.next()?;
-        let path = iter.next();
-
-        Some(Self {
-            start: parse_hex(start)?,
-            end: parse_hex(end)?,
-            permissions,
-            offset: parse_hex(offset)?,
-            device,
-            inode: inode.parse().ok()?,
-            path: path.map(str::to_owned),
-        })
+impl Parser<StdinLock<'static>, ParseMapping> {
+    /// Parses from standard input, e.g. `cat /proc/123/smaps | tool`.
+    ///
+    /// ```no_run
+    /// let regions = smaps::Parser::from_stdin()
+    ///     .entries()
+    ///     .collect::<Result<Vec<_>, _>>()?;
+    /// println!("{} regions", regions.len());
+    /// # Ok::<(), smaps::SmapsError>(())
+    /// ```
+    pub fn from_stdin() -> Self {
+        Self::new(std::io::stdin().lock())
     }
 }
 
-impl Permissions {
-    fn parse(data: &str) -> Option<Self> {
-        let data: [u8; 4] = data.as_bytes().try_into().ok()?;
-        let read = match data[0] {
-            b'-' => Permissions::empty(),
-            b'r' => Permissions::R,
-            _ => return None,
-        };
+impl<R: BufRead> Parser<R, ParseMapping> {
+    /// Parses from any buffered reader, e.g. a child process's piped
+    /// [`std::process::ChildStdout`] wrapped in a [`BufReader`], or a
+    /// [`Cursor`] over a test buffer. The other constructors are shorthands
+    /// for common readers.
+    pub fn new(reader: R) -> Self {
+        Self {
+            lines: Lines {
+                reader,
+                line: String::new(),
+                peeked: false,
+                number: 0,
+            },
+            options: Options::default(),
+            mappings: 0,
+            _state: ParseMapping,
+        }
+    }
 
-        let write = match data[1] {
-            b'-' => Permissions::empty(),
-            b'w' => Permissions::W,
-            _ => return None,
-        };
+    /// Parses from any buffered reader, e.g. a decompressed archive of a
+    /// previously captured smaps file. Same as [`Parser::new`].
+    pub fn from_reader(reader: R) -> Self {
+        Self::new(reader)
+    }
 
-        let execute = match data[2] {
-            b'-' => Permissions::empty(),
-            b'x' => Permissions::X,
-            _ => return None,
-        };
+    /// Retain the original header line in [`Mapping::raw`].
+    pub fn keep_raw(mut self) -> Self {
+        self.options.raw = true;
+        self
+    }
+
+    /// Fail on any line, key, unit, or VM flag that isn't recognized,
+    /// instead of skipping or defaulting it.
+    ///
+    /// This is meant for asserting that the crate fully models the running
+    /// kernel's output, and so will break on newer kernels by design.
+    pub fn strict(mut self) -> Self {
+        self.options.strict = true;
+        self
+    }
+
+    /// Skip lines that are neither a valid mapping header nor a recognized
+    /// usage line, instead of ending the parse.
+    ///
+    /// This allows parsing several processes' smaps concatenated into one
+    /// capture, with blank lines or banners like `=== PID 123 ===` between
+    /// them. [`Parser::strict`] takes precedence if both are set.
+    pub fn tolerant(mut self) -> Self {
+        self.options.tolerant = true;
+        self
+    }
 
-        let shared = match data[3] {
-            b's' => Permissions::S,
-            b'p' => Permissions::P,
-            _ => return None,
+    /// Stop after `limit` mappings, as if the input ended there.
+    ///
+    /// To bound the number of bytes read instead, wrap the reader in
+    /// [`std::io::Read::take`] and pass it to [`Parser::new`].
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.options.limit = Some(limit);
+        self
+    }
+
+    pub fn next(mut self) -> Result<(Parser<R, ParseUsage>, Option<Mapping>), SmapsError> {
+        if self
+            .options
+            .limit
+            .is_some_and(|limit| self.mappings >= limit)
+        {
+            return Ok((self.with_state(ParseUsage), None));
+        }
+
+        let mapping = loop {
+            match self.lines.next()? {
+                None => break None,
+                Some(line) => match Mapping::parse(line, self.options.strict) {
+                    Err(
+                        SmapsError::InvalidPermissions { .. }
+                        | SmapsError::MissingField { .. }
+                        | SmapsError::InvalidField { .. },
+                    ) if self.options.tolerant && !self.options.strict => continue,
+                    Err(error) => return Err(error),
+                    Ok(Some(mapping)) => {
+                        break Some(Mapping {
+                            raw: self.options.raw.then(|| line.to_owned()),
+                            ..mapping
+                        })
+                    }
+                    Ok(None) if self.options.strict => return Err(self.lines.malformed()),
+                    Ok(None) if self.options.tolerant => continue,
+                    Ok(None) => break None,
+                },
+            }
         };
 
-        Some(read | write | execute | shared)
+        self.mappings += mapping.is_some() as usize;
+        Ok((self.with_state(ParseUsage), mapping))
+    }
+}
+
+impl<R: BufRead> Parser<R, ParseUsage> {
+    pub fn next(mut self) -> Result<(Parser<R, ParseMapping>, Option<Usage>), SmapsError> {
+        let usage = Usage::parse(&mut self.lines, self.options)?;
+        Ok((self.with_state(ParseMapping), usage))
+    }
+
+    pub fn skip(mut self) -> Parser<R, ParseMapping> {
+        while let Ok(Some(_)) = self.lines.next_if(|line| !line.contains('-')) {}
+
+        self.with_state(ParseMapping)
     }
 }
 
-impl Device {
-    fn parse(data: &str) -> Option<Self> {
-        let (major, minor) = data.split_once(':')?;
-        Some(Self {
-            major: u32::from_str_radix(major, 16).ok()?,
-            minor: u32::from_str_radix(minor, 16).ok()?,
-        })
+impl<R: BufRead, S> Parser<R, S> {
+    fn with_state<T>(self, state: T) -> Parser<R, T> {
+        Parser {
+            lines: self.lines,
+            options: self.options,
+            mappings: self.mappings,
+            _state: state,
+        }
     }
 }
 
-fn parse_hex(data: &str) -> Option<usize> {
-    usize::from_str_radix(data, 16).ok()
+impl Usage {
+    fn parse(
+        lines: &mut Lines<impl BufRead>,
+        options: Options,
+    ) -> Result<Option<Self>, SmapsError> {
+        let mut usage = Self::default();
+
+        while let Some(line) = lines.next_if(|line| !line.contains('-'))? {
+            match usage.parse_line(line, options.strict) {
+                Ok(true) => continue,
+                Ok(false) if options.strict => return Err(lines.malformed()),
+                Ok(false) | Err(SmapsError::UnknownUnit(_)) if options.tolerant => continue,
+                Ok(false) => return Ok(None),
+                Err(error) => return Err(error),
+            }
+        }
+
+        Ok(Some(usage))
+    }
 }
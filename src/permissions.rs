@@ -0,0 +1,62 @@
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::Permissions;
+use crate::SmapsError;
+
+impl Permissions {
+    /// Checks for a mapping that is both writable and executable,
+    /// which violates W^X.
+    pub fn is_wx(&self) -> bool {
+        self.contains(Permissions::W | Permissions::X)
+    }
+
+    /// Describes how `self` differs from `expected`, e.g.
+    /// `"unexpectedly writable+executable"`, or an empty string if they match.
+    pub fn difference_description(&self, expected: Permissions) -> String {
+        let describe = |permissions: Permissions| {
+            [
+                (Permissions::R, "readable"),
+                (Permissions::W, "writable"),
+                (Permissions::X, "executable"),
+                (Permissions::S, "shared"),
+                (Permissions::P, "private"),
+            ]
+            .into_iter()
+            .filter(|(permission, _)| permissions.contains(*permission))
+            .map(|(_, name)| name)
+            .collect::<Vec<_>>()
+            .join("+")
+        };
+
+        let added = self.difference(expected);
+        let removed = expected.difference(*self);
+
+        [
+            (!added.is_empty()).then(|| format!("unexpectedly {}", describe(added))),
+            (!removed.is_empty()).then(|| format!("unexpectedly not {}", describe(removed))),
+        ]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+        .join(", ")
+    }
+}
+
+impl TryFrom<u8> for Permissions {
+    type Error = SmapsError;
+
+    /// Unlike [`Permissions::from_bits`], also requires exactly one of
+    /// shared and private, as every smaps header has.
+    fn try_from(bits: u8) -> Result<Self, Self::Error> {
+        match Permissions::from_bits(bits) {
+            Some(permissions)
+                if permissions.contains(Permissions::S) != permissions.contains(Permissions::P) =>
+            {
+                Ok(permissions)
+            }
+            _ => Err(SmapsError::InvalidPermissionBits(bits)),
+        }
+    }
+}
@@ -0,0 +1,32 @@
+use std::fs::File;
+use std::io::BufReader;
+use std::io::ErrorKind;
+
+use procfs::process::Process;
+use procfs::ProcError;
+
+use crate::parse::ParseMapping;
+use crate::Parser;
+use crate::SmapsError;
+
+impl Parser<BufReader<File>, ParseMapping> {
+    /// Opens the smaps file of a process enumerated by the `procfs` crate,
+    /// relative to its already-open `/proc/<pid>` directory, with the same
+    /// error mapping as [`Parser::from_pid`].
+    ///
+    /// Since the directory is opened by descriptor, a process that exits
+    /// is reported as gone even if its pid has since been reused.
+    pub fn from_process(process: &Process) -> Result<Self, SmapsError> {
+        match process.open_relative("smaps") {
+            Ok(file) => Ok(Self::from_file(file)),
+            Err(ProcError::Io(error, _)) => Err(SmapsError::from(error)),
+            Err(error @ ProcError::NotFound(_)) => Err(SmapsError::ProcessGone(
+                std::io::Error::new(ErrorKind::NotFound, error),
+            )),
+            Err(error @ ProcError::PermissionDenied(_)) => Err(SmapsError::PermissionDenied(
+                std::io::Error::new(ErrorKind::PermissionDenied, error),
+            )),
+            Err(error) => Err(SmapsError::Io(std::io::Error::other(error))),
+        }
+    }
+}
@@ -0,0 +1,63 @@
+use std::io::BufRead;
+use std::path::Path;
+
+use crate::parse::ParseMapping;
+
+use crate::Mapping;
+use crate::Parser;
+use crate::SmapsError;
+use crate::Usage;
+
+/// Parses all of `/proc/<pid>/smaps`, reporting a missing process as
+/// [`SmapsError::ProcessGone`] and an unreadable one as
+/// [`SmapsError::PermissionDenied`].
+pub fn read(pid: u32) -> Result<Vec<(Mapping, Usage)>, SmapsError> {
+    Parser::from_pid(pid)?.entries().collect()
+}
+
+#[cfg(feature = "rayon")]
+type ReadResult = Result<Vec<(Mapping, Usage)>, SmapsError>;
+
+/// Parses `/proc/<pid>/smaps` for each of `pids` in parallel on rayon's
+/// global thread pool, e.g. for every process on the system.
+///
+/// Processes that exit before they're read are reported as
+/// [`SmapsError::ProcessGone`], like in [`read`].
+#[cfg(feature = "rayon")]
+pub fn read_all(
+    pids: impl rayon::iter::IntoParallelIterator<Item = u32>,
+) -> Vec<(u32, ReadResult)> {
+    use rayon::iter::ParallelIterator;
+
+    pids.into_par_iter().map(|pid| (pid, read(pid))).collect()
+}
+
+/// Parses all of `/proc/<pid>/maps`, with the same error mapping as
+/// [`read`]. This is cheaper than [`read`] when usage isn't needed.
+pub fn read_maps(pid: u32) -> Result<Vec<Mapping>, SmapsError> {
+    Parser::from_proc(pid, "maps")?.mappings_only().collect()
+}
+
+/// Parses all of `/proc/self/smaps`.
+pub fn read_self() -> Result<Vec<(Mapping, Usage)>, SmapsError> {
+    Parser::open(Path::new("/proc/self/smaps"))?
+        .entries()
+        .collect()
+}
+
+/// Parses `/proc/<pid>/smaps_rollup`: a single `[rollup]` mapping spanning
+/// the whole address space, with usage summed over every mapping.
+pub fn read_rollup(pid: u32) -> Result<(Mapping, Usage), SmapsError> {
+    Parser::from_proc(pid, "smaps_rollup")?.rollup()
+}
+
+impl<R: BufRead> Parser<R, ParseMapping> {
+    /// Parses the single entry of an `smaps_rollup` file.
+    pub fn rollup(self) -> Result<(Mapping, Usage), SmapsError> {
+        self.entries().next().unwrap_or_else(|| {
+            Err(SmapsError::Io(std::io::Error::from(
+                std::io::ErrorKind::UnexpectedEof,
+            )))
+        })
+    }
+}
@@ -0,0 +1,113 @@
+use crate::mapping;
+use crate::Mapping;
+use crate::Permissions;
+use crate::Usage;
+
+/// A mapping header paired with the usage block that follows it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Region {
+    pub mapping: Mapping,
+    pub usage: Usage,
+}
+
+/// What a mapping is used for, as far as its path and inode tell.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum RegionKind {
+    /// `[heap]`
+    Heap,
+    /// `[stack]`, the main thread's stack
+    Stack,
+    /// `[stack:<tid>]`, reported for thread stacks before Linux 4.5
+    ThreadStack(u32),
+    /// `[vdso]`
+    Vdso,
+    /// `[vvar]`
+    Vvar,
+    /// `[vsyscall]`
+    Vsyscall,
+    /// no path and inode 0, or a named anonymous mapping (`[anon:...]`)
+    Anonymous,
+    /// backed by a file
+    File,
+    /// any other pseudo-path, e.g. `[uprobes]`
+    Special,
+}
+
+impl Mapping {
+    pub fn kind(&self) -> RegionKind {
+        let Some(path) = self.path.as_deref() else {
+            return match self.inode {
+                0 => RegionKind::Anonymous,
+                _ => RegionKind::File,
+            };
+        };
+
+        match path {
+            "[heap]" => RegionKind::Heap,
+            "[stack]" => RegionKind::Stack,
+            "[vdso]" => RegionKind::Vdso,
+            "[vvar]" => RegionKind::Vvar,
+            "[vsyscall]" => RegionKind::Vsyscall,
+            _ if path.starts_with("[anon:") => RegionKind::Anonymous,
+            _ if path.starts_with('[') => path
+                .strip_prefix("[stack:")
+                .and_then(|tid| tid.strip_suffix(']'))
+                .and_then(|tid| tid.parse().ok())
+                .map_or(RegionKind::Special, RegionKind::ThreadStack),
+            _ => RegionKind::File,
+        }
+    }
+
+    /// Checks for memory not backed by a file: no path and inode 0, a named
+    /// anonymous mapping, or the heap or a stack.
+    pub fn is_anonymous(&self) -> bool {
+        matches!(
+            self.kind(),
+            RegionKind::Heap
+                | RegionKind::Stack
+                | RegionKind::ThreadStack(_)
+                | RegionKind::Anonymous
+        )
+    }
+
+    /// Checks for a path with a nonzero inode, which excludes pseudo-paths
+    /// like `[heap]`.
+    pub fn is_file_backed(&self) -> bool {
+        self.inode != 0 && self.path.is_some()
+    }
+
+    /// Checks for a shared (`s`) rather than private (`p`) mapping.
+    pub fn is_shared(&self) -> bool {
+        self.permissions.contains(Permissions::S)
+    }
+}
+
+impl Region {
+    pub fn into_parts(self) -> (Mapping, Usage) {
+        (self.mapping, self.usage)
+    }
+
+    /// Heuristically detects a guard page: a private anonymous mapping with
+    /// no read, write, or execute permission, spanning exactly one page.
+    ///
+    /// The page size is `kernel_page_size` from the usage, or
+    /// [`crate::DEFAULT_PAGE_SIZE`] if it wasn't reported.
+    pub fn is_guard_page(&self) -> bool {
+        self.mapping.permissions == Permissions::P
+            && self.mapping.path.is_none()
+            && self.mapping.size() == mapping::page_size(&self.usage) as u64
+    }
+}
+
+impl From<(Mapping, Usage)> for Region {
+    fn from((mapping, usage): (Mapping, Usage)) -> Self {
+        Self { mapping, usage }
+    }
+}
+
+impl From<Region> for (Mapping, Usage) {
+    fn from(region: Region) -> Self {
+        region.into_parts()
+    }
+}
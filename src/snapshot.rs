@@ -0,0 +1,153 @@
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+use core::ops::Index;
+
+use crate::Mapping;
+use crate::Usage;
+
+/// Every region parsed from a single smaps file.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Snapshot(Vec<(Mapping, Usage)>);
+
+impl Snapshot {
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn as_slice(&self) -> &[(Mapping, Usage)] {
+        &self.0
+    }
+
+    pub fn iter(&self) -> core::slice::Iter<'_, (Mapping, Usage)> {
+        self.0.iter()
+    }
+
+    /// Sums every region's usage, for comparison against `smaps_rollup`.
+    pub fn total(&self) -> Usage {
+        self.iter().map(|(_, usage)| usage).sum()
+    }
+
+    pub fn total_pss(&self) -> usize {
+        self.iter().map(|(_, usage)| usage.pss).sum()
+    }
+
+    pub fn total_uss(&self) -> usize {
+        self.iter().map(|(_, usage)| usage.uss()).sum()
+    }
+
+    /// Returns the region whose `[start, end)` range contains `address`.
+    pub fn find(&self, address: u64) -> Option<&(Mapping, Usage)> {
+        self.iter().find(|(mapping, _)| mapping.contains(address))
+    }
+
+    /// Returns the indices of every pair of regions whose `[start, end)`
+    /// ranges overlap, each pair in ascending order.
+    pub fn find_overlaps(&self) -> Vec<(usize, usize)> {
+        let mut order = (0..self.len()).collect::<Vec<_>>();
+        order.sort_by(|&left, &right| self[left].0.cmp(&self[right].0));
+
+        let mut active = Vec::<usize>::new();
+        let mut overlaps = Vec::new();
+        for index in order {
+            let mapping = &self[index].0;
+            active.retain(|&open| self[open].0.end > mapping.start);
+            overlaps.extend(
+                active
+                    .iter()
+                    .map(|&open| (open.min(index), open.max(index))),
+            );
+            active.push(index);
+        }
+
+        overlaps.sort();
+        overlaps
+    }
+
+    /// Shifts every region down so that the lowest one starts at 0.
+    ///
+    /// This discards the absolute addresses, and is meant for diffing
+    /// address maps across ASLR'd runs rather than for live use.
+    pub fn rebase(&mut self) {
+        let Some(base) = self.iter().map(|(mapping, _)| mapping.start).min() else {
+            return;
+        };
+
+        for (mapping, _) in &mut self.0 {
+            mapping.start -= base;
+            mapping.end -= base;
+        }
+    }
+
+    /// Copies the regions matching `predicate` into a new snapshot, e.g. to
+    /// summarize only the writable mappings.
+    pub fn filter(&self, predicate: impl Fn(&(Mapping, Usage)) -> bool) -> Snapshot {
+        self.iter()
+            .filter(|region| predicate(region))
+            .cloned()
+            .collect()
+    }
+
+    /// Keeps only the regions matching `predicate`, in place.
+    pub fn retain(&mut self, predicate: impl Fn(&(Mapping, Usage)) -> bool) {
+        self.0.retain(predicate)
+    }
+
+    /// Groups file-backed and pseudo-path regions by path, in path order.
+    pub fn by_path(&self) -> BTreeMap<&str, Vec<&(Mapping, Usage)>> {
+        let mut paths = BTreeMap::<_, Vec<_>>::new();
+        for region in self {
+            if let Some(path) = region.0.path.as_deref() {
+                paths.entry(path).or_default().push(region);
+            }
+        }
+        paths
+    }
+}
+
+impl From<Vec<(Mapping, Usage)>> for Snapshot {
+    fn from(regions: Vec<(Mapping, Usage)>) -> Self {
+        Self(regions)
+    }
+}
+
+impl From<Snapshot> for Vec<(Mapping, Usage)> {
+    fn from(snapshot: Snapshot) -> Self {
+        snapshot.0
+    }
+}
+
+impl FromIterator<(Mapping, Usage)> for Snapshot {
+    fn from_iter<I: IntoIterator<Item = (Mapping, Usage)>>(iter: I) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+impl IntoIterator for Snapshot {
+    type Item = (Mapping, Usage);
+    type IntoIter = alloc::vec::IntoIter<(Mapping, Usage)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a Snapshot {
+    type Item = &'a (Mapping, Usage);
+    type IntoIter = core::slice::Iter<'a, (Mapping, Usage)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+impl Index<usize> for Snapshot {
+    type Output = (Mapping, Usage);
+
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.0[index]
+    }
+}
@@ -0,0 +1,53 @@
+use core::pin::Pin;
+use core::task::Context;
+use core::task::Poll;
+use std::io::Cursor;
+use std::path::Path;
+
+use futures_core::Stream;
+
+use crate::parse::ParseMapping;
+use crate::Entries;
+use crate::Mapping;
+use crate::Parser;
+use crate::SmapsError;
+use crate::Usage;
+
+/// Asynchronous counterpart to [`Entries`], created by
+/// [`Parser::into_stream`].
+///
+/// The file is read up front without blocking the runtime, so polling only
+/// parses lines already in memory and is always ready.
+pub struct EntryStream {
+    entries: Entries<Cursor<Vec<u8>>>,
+}
+
+impl Parser<Cursor<Vec<u8>>, ParseMapping> {
+    /// Like [`Parser::snapshot`], but reads the file with [`tokio::fs`].
+    pub async fn snapshot_async(path: &Path) -> std::io::Result<Self> {
+        tokio::fs::read(path).await.map(Cursor::new).map(Self::new)
+    }
+
+    /// Like [`Parser::from_pid`], but reads `/proc/<pid>/smaps` with
+    /// [`tokio::fs`], all at once.
+    pub async fn from_pid_async(pid: u32) -> Result<Self, SmapsError> {
+        let path = format!("/proc/{}/smaps", pid);
+        Self::snapshot_async(Path::new(&path))
+            .await
+            .map_err(SmapsError::from_proc_io)
+    }
+
+    pub fn into_stream(self) -> EntryStream {
+        EntryStream {
+            entries: self.entries(),
+        }
+    }
+}
+
+impl Stream for EntryStream {
+    type Item = Result<(Mapping, Usage), SmapsError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Poll::Ready(self.entries.next())
+    }
+}
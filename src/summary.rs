@@ -0,0 +1,78 @@
+use alloc::collections::BTreeMap;
+#[cfg(feature = "std")]
+use std::io::BufRead;
+
+#[cfg(feature = "std")]
+use crate::parse::ParseMapping;
+use crate::Mapping;
+#[cfg(feature = "std")]
+use crate::Parser;
+use crate::RegionKind;
+#[cfg(feature = "std")]
+use crate::SmapsError;
+use crate::Usage;
+
+/// Totals across every mapping of a process, in bytes.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Summary {
+    /// number of mappings
+    pub count: usize,
+    /// virtual size, summed from `Size`
+    pub size: usize,
+    /// resident set size, summed from `Rss`
+    pub rss: usize,
+    /// proportional set size, summed from `Pss`
+    pub pss: usize,
+    /// unique set size, summed from [`Usage::uss`]
+    pub uss: usize,
+    /// swapped out memory, summed from `Swap`
+    pub swap: usize,
+    /// usage summed per [`RegionKind`]
+    pub by_kind: BTreeMap<RegionKind, Usage>,
+}
+
+impl Summary {
+    /// Summarizes already-parsed regions in one pass.
+    pub fn from_entries(entries: impl IntoIterator<Item = (Mapping, Usage)>) -> Self {
+        let mut summary = Summary::default();
+        for (mapping, usage) in entries {
+            summary.add(&mapping, &usage);
+        }
+        summary
+    }
+
+    pub fn add(&mut self, mapping: &Mapping, usage: &Usage) {
+        self.count += 1;
+        self.size += usage.size;
+        self.rss += usage.rss;
+        self.pss += usage.pss;
+        self.uss += usage.uss();
+        self.swap += usage.swap;
+        self.by_kind.entry(mapping.kind()).or_default().merge(usage);
+    }
+}
+
+/// Folds every mapping from `parser` into a [`Summary`].
+#[cfg(feature = "std")]
+pub fn summarize<R: BufRead>(mut parser: Parser<R, ParseMapping>) -> Result<Summary, SmapsError> {
+    let mut summary = Summary::default();
+
+    loop {
+        let (next, mapping) = parser.next()?;
+        let Some(mapping) = mapping else {
+            return Ok(summary);
+        };
+        let (next, usage) = next.next()?;
+        parser = next;
+
+        if let Some(usage) = usage {
+            summary.add(&mapping, &usage);
+        }
+    }
+}
+
+/// Summarizes `/proc/<pid>/smaps`.
+#[cfg(feature = "std")]
+pub fn summarize_pid(pid: u32) -> Result<Summary, SmapsError> {
+    summarize(Parser::from_pid(pid)?)
+}
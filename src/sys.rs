@@ -0,0 +1,26 @@
+use crate::Permissions;
+
+impl Permissions {
+    /// Converts to the `PROT_*` flags accepted by `mmap` and `mprotect`.
+    pub fn to_prot_flags(&self) -> i32 {
+        [
+            (Permissions::R, libc::PROT_READ),
+            (Permissions::W, libc::PROT_WRITE),
+            (Permissions::X, libc::PROT_EXEC),
+        ]
+        .into_iter()
+        .filter(|(permission, _)| self.contains(*permission))
+        .fold(libc::PROT_NONE, |flags, (_, prot)| flags | prot)
+    }
+
+    /// Converts the sharing bit to `MAP_SHARED` or `MAP_PRIVATE`.
+    pub fn to_map_flags(&self) -> i32 {
+        if self.contains(Permissions::S) {
+            libc::MAP_SHARED
+        } else if self.contains(Permissions::P) {
+            libc::MAP_PRIVATE
+        } else {
+            0
+        }
+    }
+}
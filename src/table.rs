@@ -0,0 +1,60 @@
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::format::format_bytes;
+use crate::Snapshot;
+
+/// A `pmap -x`-style table of a [`Snapshot`], created by
+/// [`Snapshot::display_table`].
+pub struct Table<'a> {
+    snapshot: &'a Snapshot,
+    sort_by_pss: bool,
+}
+
+impl Snapshot {
+    pub fn display_table(&self) -> Table<'_> {
+        Table {
+            snapshot: self,
+            sort_by_pss: false,
+        }
+    }
+}
+
+impl Table<'_> {
+    /// List regions by descending PSS instead of by address.
+    pub fn sort_by_pss(mut self) -> Self {
+        self.sort_by_pss = true;
+        self
+    }
+}
+
+impl fmt::Display for Table<'_> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        let mut regions = self.snapshot.iter().collect::<Vec<_>>();
+        if self.sort_by_pss {
+            regions.sort_by(|left, right| crate::by_pss(left, right));
+        }
+
+        writeln!(
+            fmt,
+            "{:<33}  {:<4}  {:>10}  {:>10}  Path",
+            "Address", "Perm", "Size", "PSS",
+        )?;
+
+        for (mapping, usage) in regions {
+            writeln!(
+                fmt,
+                "{:016x}-{:016x}  {}  {:>10}  {:>10}  {}{}",
+                mapping.start,
+                mapping.end,
+                mapping.permissions,
+                format_bytes(usize::try_from(mapping.size()).unwrap_or(usize::MAX)),
+                format_bytes(usage.pss),
+                mapping.path.as_deref().unwrap_or("[anon]"),
+                if mapping.deleted { " (deleted)" } else { "" },
+            )?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,56 @@
+use core::cmp::Reverse;
+use std::collections::BTreeMap;
+use std::collections::BinaryHeap;
+use std::io::BufRead;
+
+use crate::parse::ParseMapping;
+use crate::Parser;
+use crate::SmapsError;
+use crate::Usage;
+use crate::UsageField;
+
+/// Returns the `n` paths with the largest summed `field` across all of their
+/// mappings, largest first, consuming `parser` in a single pass.
+///
+/// Only the running total per path is kept while parsing, and selection
+/// holds at most `n` candidates at a time. Paths with equal totals keep
+/// their sorted order. If `n` is 0, `parser` isn't read at all.
+pub fn top_paths<R: BufRead>(
+    mut parser: Parser<R, ParseMapping>,
+    field: UsageField,
+    n: usize,
+) -> Result<Vec<(String, Usage)>, SmapsError> {
+    if n == 0 {
+        return Ok(Vec::new());
+    }
+
+    let mut paths = BTreeMap::<String, Usage>::new();
+
+    loop {
+        let (next, mapping) = parser.next()?;
+        let Some(mapping) = mapping else {
+            break;
+        };
+        let (next, usage) = next.next()?;
+        parser = next;
+
+        if let (Some(path), Some(usage)) = (mapping.path, usage) {
+            paths.entry(path).or_default().merge(&usage);
+        }
+    }
+
+    let mut paths = paths.into_iter().map(Some).collect::<Vec<_>>();
+    let mut heap = BinaryHeap::with_capacity(n.min(paths.len()) + 1);
+    for (index, (_, usage)) in paths.iter().flatten().enumerate() {
+        heap.push(Reverse((field.get(usage), Reverse(index))));
+        if heap.len() > n {
+            heap.pop();
+        }
+    }
+
+    Ok(heap
+        .into_sorted_vec()
+        .into_iter()
+        .filter_map(|Reverse((_, Reverse(index)))| paths[index].take())
+        .collect())
+}
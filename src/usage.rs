@@ -0,0 +1,360 @@
+use alloc::vec::Vec;
+use core::iter::Sum;
+use core::ops::Add;
+use core::ops::AddAssign;
+
+use crate::Usage;
+use crate::DEFAULT_PAGE_SIZE;
+
+/// Replaces a page size of 0, which would divide by zero, with
+/// [`DEFAULT_PAGE_SIZE`].
+fn or_default(page_size: usize) -> usize {
+    match page_size {
+        0 => DEFAULT_PAGE_SIZE,
+        page_size => page_size,
+    }
+}
+
+/// Resident memory split into shared/private × clean/dirty.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct RssBreakdown {
+    pub shared_clean: usize,
+    pub shared_dirty: usize,
+    pub private_clean: usize,
+    pub private_dirty: usize,
+    /// sum of the four quadrants, which matches `rss` outside of hugetlb pages
+    pub total: usize,
+}
+
+/// The largest contributor to a mapping's footprint, for at-a-glance triage.
+///
+/// Ties go to the variant declared first, so that the costlier kind of
+/// memory wins.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum DominantKind {
+    /// private dirty pages, e.g. heap and stack
+    PrivateDirty,
+    /// swapped out pages
+    Swap,
+    /// dirty pages shared with other processes, e.g. shared memory
+    SharedDirty,
+    /// clean pages only this process maps, e.g. freshly read file data
+    PrivateClean,
+    /// clean pages shared with other processes, e.g. library code
+    SharedClean,
+    /// nothing resident or swapped out
+    Empty,
+}
+
+/// An invariant between [`Usage`] fields that doesn't hold.
+///
+/// All invariants are strict unless noted otherwise.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Inconsistency {
+    /// `rss > size`
+    RssExceedsSize,
+    /// `pss > rss`
+    PssExceedsRss,
+    /// `pss_dirty > pss`
+    PssDirtyExceedsPss,
+    /// `referenced > rss`
+    ReferencedExceedsRss,
+    /// `anonymous > rss`
+    AnonymousExceedsRss,
+    /// `lazy_free > anonymous`
+    LazyFreeExceedsAnonymous,
+    /// `anon_huge_pages > anonymous`
+    AnonHugePagesExceedsAnonymous,
+    /// `swap_pss > swap`
+    SwapPssExceedsSwap,
+    /// shared/private × clean/dirty doesn't sum to `rss`
+    ///
+    /// Approximate: hugetlb pages are accounted separately, so this is only
+    /// checked when `shared_hugetlb` and `private_hugetlb` are both zero.
+    RssBreakdownMismatch,
+}
+
+impl Usage {
+    pub fn rss_breakdown(&self) -> RssBreakdown {
+        RssBreakdown {
+            shared_clean: self.shared_clean,
+            shared_dirty: self.shared_dirty,
+            private_clean: self.private_clean,
+            private_dirty: self.private_dirty,
+            total: self.shared_clean + self.shared_dirty + self.private_clean + self.private_dirty,
+        }
+    }
+
+    /// Classifies this mapping by which of the RSS quadrants or swap is largest.
+    pub fn dominant_kind(&self) -> DominantKind {
+        let breakdown = self.rss_breakdown();
+        [
+            (breakdown.private_dirty, DominantKind::PrivateDirty),
+            (self.swap, DominantKind::Swap),
+            (breakdown.shared_dirty, DominantKind::SharedDirty),
+            (breakdown.private_clean, DominantKind::PrivateClean),
+            (breakdown.shared_clean, DominantKind::SharedClean),
+        ]
+        .into_iter()
+        .filter(|(size, _)| *size > 0)
+        .fold(
+            None,
+            |dominant: Option<(usize, DominantKind)>, (size, kind)| match dominant {
+                Some((largest, _)) if largest >= size => dominant,
+                _ => Some((size, kind)),
+            },
+        )
+        .map_or(DominantKind::Empty, |(_, kind)| kind)
+    }
+
+    /// Largest memory protection key accepted by the parser, matching the
+    /// 16 keys x86 provides.
+    pub const MAX_PROTECTION_KEY: usize = 15;
+
+    /// The memory protection key (`pkey_mprotect(2)`) assigned to the
+    /// mapping, in `0..=MAX_PROTECTION_KEY`.
+    ///
+    /// `None` if the kernel wasn't built with protection key support or
+    /// the CPU lacks it, in which case the `ProtectionKey` line is absent.
+    /// Key 0 is the default key every mapping starts with.
+    pub fn protection_key(&self) -> Option<usize> {
+        self.protection_key
+    }
+
+    /// Looks up a field by its smaps key, e.g. `"Pss"`.
+    ///
+    /// `THPeligible` is returned as 0 or 1, and `VmFlags` isn't available.
+    pub fn get_field(&self, key: &str) -> Option<usize> {
+        match key {
+            "THPeligible" => self.thp_eligible.map(usize::from),
+            "ProtectionKey" => self.protection_key,
+            _ => self
+                .sizes()
+                .into_iter()
+                .find(|(name, _)| *name == key)
+                .map(|(_, value)| value),
+        }
+    }
+
+    /// Every size field paired with its smaps key, in the kernel's order.
+    pub(crate) fn sizes(&self) -> [(&'static str, usize); 22] {
+        [
+            ("Size", self.size),
+            ("KernelPageSize", self.kernel_page_size),
+            ("MMUPageSize", self.mmu_page_size),
+            ("Rss", self.rss),
+            ("Pss", self.pss),
+            ("Pss_Dirty", self.pss_dirty),
+            ("Shared_Clean", self.shared_clean),
+            ("Shared_Dirty", self.shared_dirty),
+            ("Private_Clean", self.private_clean),
+            ("Private_Dirty", self.private_dirty),
+            ("Referenced", self.referenced),
+            ("Anonymous", self.anonymous),
+            ("KSM", self.ksm),
+            ("LazyFree", self.lazy_free),
+            ("AnonHugePages", self.anon_huge_pages),
+            ("ShmemPmdMapped", self.shmem_pmd_mapped),
+            ("FilePmdMapped", self.file_pmd_mapped),
+            ("Shared_Hugetlb", self.shared_hugetlb),
+            ("Private_Hugetlb", self.private_hugetlb),
+            ("Swap", self.swap),
+            ("SwapPss", self.swap_pss),
+            ("Locked", self.locked),
+        ]
+    }
+
+    /// Unique set size: memory that would be freed if this mapping went away.
+    pub fn uss(&self) -> usize {
+        self.private_clean + self.private_dirty
+    }
+
+    /// Counts the `page_size` pages spanned by `field`, rounding up.
+    ///
+    /// A `page_size` of 0 is treated as unreported, like a missing
+    /// `KernelPageSize`, and replaced with [`DEFAULT_PAGE_SIZE`].
+    pub fn pages(&self, field: impl Fn(&Usage) -> usize, page_size: usize) -> usize {
+        field(self).div_ceil(or_default(page_size))
+    }
+
+    /// Rounds every size field up to a whole number of `page_size` pages,
+    /// and reports `page_size` as both the kernel and MMU page size.
+    ///
+    /// This is approximate: it estimates the footprint on a system with a
+    /// different page size, for comparing usage across machines (e.g. 4K
+    /// and 64K page systems). `thp_eligible`, `protection_key`, and the VM
+    /// flags are unchanged. A `page_size` of 0 is replaced with
+    /// [`DEFAULT_PAGE_SIZE`], as in [`Usage::pages`].
+    pub fn normalize(&self, page_size: usize) -> Usage {
+        let page_size = or_default(page_size);
+        let round = |bytes: usize| bytes.next_multiple_of(page_size);
+
+        Usage {
+            size: round(self.size),
+            kernel_page_size: page_size,
+            mmu_page_size: page_size,
+            rss: round(self.rss),
+            pss: round(self.pss),
+            pss_dirty: round(self.pss_dirty),
+            shared_clean: round(self.shared_clean),
+            shared_dirty: round(self.shared_dirty),
+            private_clean: round(self.private_clean),
+            private_dirty: round(self.private_dirty),
+            referenced: round(self.referenced),
+            anonymous: round(self.anonymous),
+            ksm: round(self.ksm),
+            lazy_free: round(self.lazy_free),
+            anon_huge_pages: round(self.anon_huge_pages),
+            shmem_huge_pages: round(self.shmem_huge_pages),
+            shmem_pmd_mapped: round(self.shmem_pmd_mapped),
+            file_pmd_mapped: round(self.file_pmd_mapped),
+            shared_hugetlb: round(self.shared_hugetlb),
+            private_hugetlb: round(self.private_hugetlb),
+            swap: round(self.swap),
+            swap_pss: round(self.swap_pss),
+            locked: round(self.locked),
+            thp_eligible: self.thp_eligible,
+            protection_key: self.protection_key,
+            mixed_protection_keys: self.mixed_protection_keys,
+            vm_flags: self.vm_flags,
+            unknown_vm_flags: self.unknown_vm_flags.clone(),
+        }
+    }
+
+    /// Checks the relationships the kernel maintains between fields,
+    /// returning every one that is violated.
+    pub fn validate(&self) -> Result<(), Vec<Inconsistency>> {
+        let hugetlb = self.shared_hugetlb + self.private_hugetlb;
+        let inconsistencies = [
+            (self.rss > self.size, Inconsistency::RssExceedsSize),
+            (self.pss > self.rss, Inconsistency::PssExceedsRss),
+            (self.pss_dirty > self.pss, Inconsistency::PssDirtyExceedsPss),
+            (
+                self.referenced > self.rss,
+                Inconsistency::ReferencedExceedsRss,
+            ),
+            (
+                self.anonymous > self.rss,
+                Inconsistency::AnonymousExceedsRss,
+            ),
+            (
+                self.lazy_free > self.anonymous,
+                Inconsistency::LazyFreeExceedsAnonymous,
+            ),
+            (
+                self.anon_huge_pages > self.anonymous,
+                Inconsistency::AnonHugePagesExceedsAnonymous,
+            ),
+            (self.swap_pss > self.swap, Inconsistency::SwapPssExceedsSwap),
+            (
+                hugetlb == 0 && self.rss_breakdown().total != self.rss,
+                Inconsistency::RssBreakdownMismatch,
+            ),
+        ]
+        .into_iter()
+        .filter_map(|(violated, inconsistency)| violated.then_some(inconsistency))
+        .collect::<Vec<_>>();
+
+        if inconsistencies.is_empty() {
+            Ok(())
+        } else {
+            Err(inconsistencies)
+        }
+    }
+
+    /// Adds `other` into `self` field by field, for accumulating totals.
+    ///
+    /// Page sizes keep the larger of the two, `thp_eligible` and `vm_flags`
+    /// are ORed, `protection_key` becomes `None` for good once any two keys
+    /// differ (see [`Usage::mixed_protection_keys`]), and unknown VM flags
+    /// are added if not already present. A missing `thp_eligible`,
+    /// `vm_flags`, or `protection_key` defers to the other side, so that
+    /// [`Usage::default`] doesn't change a total.
+    pub fn merge(&mut self, other: &Usage) {
+        self.size += other.size;
+        self.kernel_page_size = self.kernel_page_size.max(other.kernel_page_size);
+        self.mmu_page_size = self.mmu_page_size.max(other.mmu_page_size);
+        self.rss += other.rss;
+        self.pss += other.pss;
+        self.pss_dirty += other.pss_dirty;
+        self.shared_clean += other.shared_clean;
+        self.shared_dirty += other.shared_dirty;
+        self.private_clean += other.private_clean;
+        self.private_dirty += other.private_dirty;
+        self.referenced += other.referenced;
+        self.anonymous += other.anonymous;
+        self.ksm += other.ksm;
+        self.lazy_free += other.lazy_free;
+        self.anon_huge_pages += other.anon_huge_pages;
+        self.shmem_huge_pages += other.shmem_huge_pages;
+        self.shmem_pmd_mapped += other.shmem_pmd_mapped;
+        self.file_pmd_mapped += other.file_pmd_mapped;
+        self.shared_hugetlb += other.shared_hugetlb;
+        self.private_hugetlb += other.private_hugetlb;
+        self.swap += other.swap;
+        self.swap_pss += other.swap_pss;
+        self.locked += other.locked;
+        self.thp_eligible = match (self.thp_eligible, other.thp_eligible) {
+            (Some(left), Some(right)) => Some(left || right),
+            (left, right) => left.or(right),
+        };
+        // Conflicting keys stick, so that a later key can't overwrite the
+        // `None` and the result doesn't depend on the order of merges
+        self.mixed_protection_keys |= other.mixed_protection_keys
+            || matches!(
+                (self.protection_key, other.protection_key),
+                (Some(left), Some(right)) if left != right
+            );
+        self.protection_key = if self.mixed_protection_keys {
+            None
+        } else {
+            self.protection_key.or(other.protection_key)
+        };
+        self.vm_flags = match (self.vm_flags, other.vm_flags) {
+            (Some(left), Some(right)) => Some(left | right),
+            (left, right) => left.or(right),
+        };
+        for flag in &other.unknown_vm_flags {
+            if !self.unknown_vm_flags.contains(flag) {
+                self.unknown_vm_flags.push(flag.clone());
+            }
+        }
+    }
+}
+
+/// Combines usage with the same semantics as [`Usage::merge`].
+impl Add for Usage {
+    type Output = Usage;
+
+    fn add(mut self, other: Usage) -> Usage {
+        self.merge(&other);
+        self
+    }
+}
+
+impl AddAssign for Usage {
+    fn add_assign(&mut self, other: Usage) {
+        self.merge(&other);
+    }
+}
+
+impl AddAssign<&Usage> for Usage {
+    fn add_assign(&mut self, other: &Usage) {
+        self.merge(other);
+    }
+}
+
+impl<'a> Sum<&'a Usage> for Usage {
+    fn sum<I: Iterator<Item = &'a Usage>>(iter: I) -> Self {
+        iter.fold(Usage::default(), |mut total, usage| {
+            total.merge(usage);
+            total
+        })
+    }
+}
+
+impl Sum for Usage {
+    fn sum<I: Iterator<Item = Usage>>(iter: I) -> Self {
+        iter.fold(Usage::default(), Add::add)
+    }
+}
@@ -0,0 +1,37 @@
+use crate::VmFlags;
+
+impl VmFlags {
+    /// Executable and not writable.
+    pub fn is_code(&self) -> bool {
+        self.contains(VmFlags::EX) && !self.contains(VmFlags::WR)
+    }
+
+    /// Writable and not executable.
+    pub fn is_data(&self) -> bool {
+        self.contains(VmFlags::WR) && !self.contains(VmFlags::EX)
+    }
+
+    pub fn is_shared_mapping(&self) -> bool {
+        self.contains(VmFlags::SH)
+    }
+
+    /// Registered with userfaultfd for missing page (`um`) or write-protect
+    /// (`uw`) tracking, as used by live migration and CRIU's lazy restore.
+    pub fn is_userfaultfd(&self) -> bool {
+        self.intersects(VmFlags::UM | VmFlags::UW)
+    }
+
+    /// Checks the `ar` flag, whose meaning depends on the architecture the
+    /// smaps was captured on:
+    ///
+    /// - x86: `VM_PAT`, a PFN mapping with page attribute table caching
+    /// - powerpc: `VM_SAO`, strong access ordering
+    /// - sparc64: `VM_SPARC_ADI`, application data integrity
+    /// - parisc: `VM_GROWSUP`, a stack that grows upward
+    ///
+    /// Other architectures don't use it. Note that x86 protection keys are
+    /// reported separately as `ProtectionKey`, not through this flag.
+    pub fn is_arch_specific(&self) -> bool {
+        self.contains(VmFlags::AR)
+    }
+}
@@ -0,0 +1,47 @@
+use std::alloc::GlobalAlloc;
+use std::alloc::Layout;
+use std::alloc::System;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+
+use smaps::Parser;
+
+/// Counts allocations. This is the only test in its binary, so no other
+/// thread allocates concurrently.
+struct Counting;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+#[test]
+fn line_buffer_is_reused() {
+    let smaps = include_str!("data/smaps").repeat(100);
+    let mappings = smaps.lines().filter(|line| line.contains('-')).count();
+    let lines = smaps.lines().count();
+
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    let mut count = 0;
+    for entry in Parser::from_bytes(smaps.as_bytes()) {
+        drop(entry.unwrap());
+        count += 1;
+    }
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed) - before;
+
+    // Only each mapping's path should be allocated, plus a few buffers
+    assert_eq!(count, mappings);
+    assert!(allocations < mappings + 16, "{} allocations", allocations);
+    assert!(allocations < lines / 10, "{} allocations", allocations);
+}
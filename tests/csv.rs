@@ -0,0 +1,48 @@
+#![cfg(feature = "csv")]
+
+use smaps::Parser;
+
+#[test]
+fn csv_rows() {
+    let smaps = "\
+00400000-004ef000 r-xp 00000000 fd:00 1835051 /usr/bin/bash
+Rss:                 600 kB
+Pss:                 300 kB
+Private_Dirty:         4 kB
+7f0000000000-7f0000001000 rw-p 00000000 fd:00 1835052 /tmp/a, \"b\".bin
+Swap:                  8 kB
+7ffc00000000-7ffc00021000 rw-p 00000000 00:00 0 [stack]
+";
+    let entries = Parser::from_reader(smaps.as_bytes())
+        .entries()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+
+    let mut buffer = Vec::new();
+    smaps::write_csv(&entries, &mut buffer).unwrap();
+
+    let mut reader = csv::Reader::from_reader(buffer.as_slice());
+    let header = reader.headers().unwrap().clone();
+    assert_eq!(&header[0], "start");
+    assert_eq!(&header[5], "kind");
+
+    let rows = reader.records().collect::<Result<Vec<_>, _>>().unwrap();
+    assert_eq!(rows.len(), entries.len());
+    assert_eq!(
+        &rows[0].iter().take(8).collect::<Vec<_>>(),
+        &[
+            "0x400000",
+            "0x4ef000",
+            "978944",
+            "r-xp",
+            "/usr/bin/bash",
+            "file",
+            "614400",
+            "307200"
+        ]
+    );
+    assert_eq!(&rows[0][12], "4096");
+    assert_eq!(&rows[1][4], "/tmp/a, \"b\".bin");
+    assert_eq!(&rows[1][8], "8192");
+    assert_eq!(&rows[2][5], "stack");
+}
@@ -0,0 +1,11 @@
+use smaps::format_bytes;
+
+#[test]
+fn iec_units() {
+    assert_eq!(format_bytes(0), "0 B");
+    assert_eq!(format_bytes(1023), "1023 B");
+    assert_eq!(format_bytes(1024), "1.0 KiB");
+    assert_eq!(format_bytes(1536), "1.5 KiB");
+    assert_eq!(format_bytes(4608 << 10), "4.5 MiB");
+    assert_eq!(format_bytes(3 << 30), "3.0 GiB");
+}
@@ -0,0 +1,104 @@
+#![cfg(feature = "serde")]
+
+use smaps::Mapping;
+use smaps::Parser;
+use smaps::Permissions;
+use smaps::Region;
+use smaps::Usage;
+use smaps::VmFlags;
+
+#[test]
+fn jsonl_round_trip() {
+    let smaps = include_str!("data/smaps-5.15");
+    let mut parser = Parser::from_reader(smaps.as_bytes());
+    let mut entries: Vec<(Mapping, Usage)> = Vec::new();
+
+    while let (next, Some(mapping)) = parser.next().unwrap() {
+        let (next, usage) = next.next().unwrap();
+        entries.push((mapping, usage.unwrap()));
+        parser = next;
+    }
+
+    let mut buffer = Vec::new();
+    smaps::write_jsonl(&entries, &mut buffer).unwrap();
+    let lines = String::from_utf8(buffer).unwrap();
+
+    assert_eq!(lines.lines().count(), entries.len());
+    for (line, entry) in lines.lines().zip(entries) {
+        let region = serde_json::from_str::<Region>(line).unwrap();
+        assert_eq!(region.into_parts(), entry);
+    }
+}
+
+#[test]
+fn usage_missing_fields() {
+    let usage = serde_json::from_str::<Usage>(r#"{"rss": 4096, "pss": 2048}"#).unwrap();
+    assert_eq!(
+        usage,
+        Usage {
+            rss: 4096,
+            pss: 2048,
+            ..Usage::default()
+        }
+    );
+}
+
+#[test]
+fn flags_as_strings() {
+    let smaps =
+        "00400000-004ef000 r-xp 00000000 fd:00 1835051 /usr/bin/bash\nVmFlags: rd ex mr mw me\n";
+    let (mapping, usage) = Parser::from_reader(smaps.as_bytes())
+        .entries()
+        .next()
+        .unwrap()
+        .unwrap();
+
+    let json = serde_json::to_value(&mapping).unwrap();
+    assert_eq!(json["permissions"], "r-xp");
+    let json = serde_json::to_value(&usage).unwrap();
+    assert_eq!(json["vm_flags"], "rd ex mr mw me");
+
+    assert_eq!(
+        serde_json::from_str::<Permissions>(r#""rw-s""#).unwrap(),
+        Permissions::R | Permissions::W | Permissions::S
+    );
+    assert_eq!(
+        serde_json::from_str::<VmFlags>(r#""""#).unwrap(),
+        VmFlags::empty()
+    );
+    assert!(serde_json::from_str::<Permissions>(r#""rwxq""#).is_err());
+    assert!(serde_json::from_str::<VmFlags>(r#""rd zz""#).is_err());
+}
+
+#[test]
+fn json_document() {
+    let smaps = include_str!("data/smaps-5.15");
+    let entries = Parser::from_reader(smaps.as_bytes())
+        .entries()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+
+    let json = serde_json::from_str::<serde_json::Value>(&smaps::to_json(&entries)).unwrap();
+    let regions = json["regions"].as_array().unwrap();
+    assert_eq!(regions.len(), entries.len());
+    for (region, (mapping, usage)) in regions.iter().zip(&entries) {
+        let hex = |value: u64| format!("{:#x}", value);
+        assert_eq!(region["mapping"]["start"], hex(mapping.start));
+        assert_eq!(region["mapping"]["end"], hex(mapping.end));
+        assert_eq!(region["mapping"]["offset"], hex(mapping.offset));
+        assert_eq!(
+            region["mapping"]["permissions"],
+            mapping.permissions.to_string()
+        );
+        assert!(region["mapping"].get("raw").is_none());
+        assert_eq!(region["usage"]["rss"], usage.rss);
+    }
+
+    let total = entries.iter().map(|(_, usage)| usage.pss).sum::<usize>();
+    assert_eq!(json["summary"]["count"], entries.len());
+    assert_eq!(json["summary"]["pss"], total);
+
+    let empty = serde_json::from_str::<serde_json::Value>(&smaps::to_json(&[])).unwrap();
+    assert_eq!(empty["regions"], serde_json::json!([]));
+    assert_eq!(empty["summary"]["rss"], 0);
+}
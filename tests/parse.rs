@@ -0,0 +1,1264 @@
+use std::fmt::Write;
+use std::io::BufReader;
+use std::io::Cursor;
+use std::io::Read;
+use std::path::Path;
+
+use smaps::Device;
+use smaps::Mapping;
+use smaps::Parser;
+use smaps::Permissions;
+use smaps::Region;
+use smaps::RegionKind;
+use smaps::SmapsError;
+use smaps::Usage;
+use smaps::VmFlags;
+
+#[test]
+fn limit_stops_early() {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/data/smaps");
+    let mut parser = Parser::open(&path).unwrap().limit(3);
+    let mut count = 0;
+
+    while let (next, Some(_)) = parser.next().unwrap() {
+        parser = next.skip();
+        count += 1;
+    }
+
+    assert_eq!(count, 3);
+}
+
+#[test]
+fn tolerant_skips_banners() {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/data/smaps");
+    let smaps = std::fs::read_to_string(path).unwrap();
+    let capture = format!("=== PID 1 ===\n{}\n=== PID 2 ===\n\n{}", smaps, smaps);
+    let mut parser = Parser::from_reader(capture.as_bytes()).tolerant();
+    let mut count = 0;
+
+    while let (next, Some(_)) = parser.next().unwrap() {
+        let (next, usage) = next.next().unwrap();
+        assert!(usage.is_some());
+        parser = next;
+        count += 1;
+    }
+
+    assert_eq!(
+        count,
+        2 * smaps.lines().filter(|line| line.contains('-')).count()
+    );
+}
+
+fn parse(name: &str, strict: bool) -> Vec<(Mapping, Usage)> {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/data")
+        .join(name);
+    let mut parser = Parser::open(&path).unwrap();
+    if strict {
+        parser = parser.strict();
+    }
+    parser.entries().collect::<Result<_, _>>().unwrap()
+}
+
+#[test]
+fn cursor() {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/data/smaps");
+    let buffer = std::fs::read(path).unwrap();
+    let parser = Parser::new(Cursor::new(buffer)).strict();
+    let regions = parser.entries().collect::<Result<Vec<_>, _>>().unwrap();
+    assert_eq!(regions, parse("smaps", true));
+}
+
+#[test]
+fn malformed_line_number() {
+    let smaps = "\
+00400000-004ef000 r-xp 00000000 fd:00 1835051
+Rss:  4 kB
+Pss:  4 kB
+00600000-00601000 rw-p 00000000 00:00 0
+Rss:  4 kB
+garbage
+";
+    let (next, _) = Parser::from_reader(smaps.as_bytes())
+        .strict()
+        .next()
+        .unwrap();
+    let (next, _) = next.skip().next().unwrap();
+    match next.next() {
+        Err(SmapsError::Malformed { line_number, line }) => {
+            assert_eq!(line_number, 6);
+            assert_eq!(line, "garbage");
+        }
+        other => panic!(
+            "Expected malformed line: {:?}",
+            other.map(|(_, usage)| usage)
+        ),
+    }
+}
+
+#[test]
+fn kernel_versions() {
+    let linux_3_10 = parse("smaps-3.10", true);
+    assert_eq!(linux_3_10.len(), 3);
+    assert!(linux_3_10[2].1.vm_flags.unwrap().contains(VmFlags::NL));
+    for (_, usage) in &linux_3_10 {
+        assert!(usage.rss > 0);
+        assert_eq!(usage.lazy_free, 0);
+        assert_eq!(usage.swap_pss, 0);
+        assert_eq!(usage.pss_dirty, 0);
+        assert_eq!(usage.thp_eligible, None);
+        assert_eq!(usage.protection_key, None);
+    }
+
+    let linux_4_19 = parse("smaps-4.19", true);
+    assert_eq!(linux_4_19.len(), 2);
+    assert_eq!(linux_4_19[1].1.swap_pss, 16 << 10);
+    assert_eq!(linux_4_19[1].1.thp_eligible, None);
+
+    let linux_5_15 = parse("smaps-5.15", true);
+    assert_eq!(linux_5_15.len(), 2);
+    assert_eq!(linux_5_15[0].1.thp_eligible, Some(false));
+    assert_eq!(linux_5_15[1].1.thp_eligible, Some(true));
+    assert_eq!(linux_5_15[1].1.protection_key, Some(0));
+    assert_eq!(linux_5_15[1].1.ksm, 0);
+
+    let linux_6 = parse("smaps", true);
+    assert!(linux_6
+        .iter()
+        .all(|(_, usage)| usage.protection_key.is_some()));
+}
+
+#[test]
+fn protection_key() {
+    let mut usage = Usage::default();
+    assert!(usage.parse_line("ProtectionKey:         3", true).unwrap());
+    assert_eq!(usage.protection_key(), Some(3));
+    assert!(usage.parse_line("ProtectionKey: 0xf", true).unwrap());
+    assert_eq!(usage.protection_key(), Some(Usage::MAX_PROTECTION_KEY));
+
+    for key in ["16", "0x10", "three", ""] {
+        let line = format!("ProtectionKey: {}", key);
+        match Usage::default().parse_line(&line, false) {
+            Err(SmapsError::InvalidField { field, .. }) => assert_eq!(field, "ProtectionKey"),
+            other => panic!("Expected invalid key for {:?}: {:?}", key, other),
+        }
+    }
+}
+
+#[test]
+fn typed_errors() {
+    let usage = |body: &str, strict: bool| {
+        let smaps = format!("00400000-004ef000 r-xp 00000000 fd:00 1835051\n{}\n", body);
+        let mut parser = Parser::from_reader(Cursor::new(smaps));
+        if strict {
+            parser = parser.strict();
+        }
+        parser.next().unwrap().0.next().map(|(_, usage)| usage)
+    };
+
+    assert!(matches!(
+        usage("Rss: 4 pB", false),
+        Err(SmapsError::UnknownUnit(unit)) if unit == "pB"
+    ));
+    assert!(matches!(
+        usage("FutureField: 4 kB", true),
+        Err(SmapsError::UnknownKey(key)) if key == "FutureField"
+    ));
+    assert!(matches!(
+        usage("VmFlags: rd zz", true),
+        Err(SmapsError::UnknownVmFlag(flag)) if flag == "zz"
+    ));
+    assert!(matches!(
+        usage("Rss: four kB", true),
+        Err(SmapsError::Malformed { line_number: 2, line }) if line == "Rss: four kB"
+    ));
+
+    struct Failing;
+    impl Read for Failing {
+        fn read(&mut self, _: &mut [u8]) -> std::io::Result<usize> {
+            Err(std::io::Error::other("disconnected"))
+        }
+    }
+    assert!(matches!(
+        Parser::from_reader(BufReader::new(Failing)).next(),
+        Err(SmapsError::Io(_))
+    ));
+}
+
+#[test]
+fn units() {
+    for (line, expected) in [
+        ("Rss: 4 kB", 4 << 10),
+        ("Rss: 4 KB", 4 << 10),
+        ("Rss: 4 kb", 4 << 10),
+        ("Rss: 4 KiB", 4 << 10),
+        ("Rss: 4 MiB", 4 << 20),
+        ("Rss: 4 mB", 4 << 20),
+        ("Rss: 4 GIB", 4 << 30),
+        ("Rss: 4 B", 4),
+        ("Rss: 4", 4),
+    ] {
+        let mut usage = Usage::default();
+        assert!(usage.parse_line(line, true).unwrap(), "{}", line);
+        assert_eq!(usage.rss, expected, "{}", line);
+    }
+
+    for unit in ["pB", "kiB2", "bytes"] {
+        let line = format!("Rss: 4 {}", unit);
+        assert!(matches!(
+            Usage::default().parse_line(&line, false),
+            Err(SmapsError::UnknownUnit(found)) if found == unit
+        ));
+    }
+}
+
+#[test]
+fn unknown_fields_are_ignored() {
+    let smaps = "\
+00400000-004ef000 r-xp 00000000 fd:00 1835051 /usr/bin/bash
+Size:                956 kB
+Nonlinear:             0 kB
+Rss:                 600 kB
+FutureField:          42 kB
+Pss:                 300 kB
+VmFlags: rd ex mr mw me zz 
+";
+    let (next, mapping) = Parser::from_reader(smaps.as_bytes()).next().unwrap();
+    assert!(mapping.is_some());
+    let usage = next.next().unwrap().1.unwrap();
+    assert_eq!(usage.size, 956 << 10);
+    assert_eq!(usage.rss, 600 << 10);
+    assert_eq!(usage.pss, 300 << 10);
+    assert_eq!(
+        usage.vm_flags,
+        Some(VmFlags::RD | VmFlags::EX | VmFlags::MR | VmFlags::MW | VmFlags::ME)
+    );
+    assert_eq!(usage.unknown_vm_flags, ["zz"]);
+
+    let (next, _) = Parser::from_reader(smaps.as_bytes())
+        .strict()
+        .next()
+        .unwrap();
+    assert!(matches!(next.next(), Err(SmapsError::UnknownKey(_))));
+}
+
+#[test]
+fn unknown_vm_flags_are_preserved() {
+    let smaps = "00400000-004ef000 r-xp 00000000 fd:00 1835051\nVmFlags: rd wr zz qq\n";
+    let (next, _) = Parser::from_reader(smaps.as_bytes()).next().unwrap();
+    let usage = next.next().unwrap().1.unwrap();
+    assert_eq!(usage.vm_flags, Some(VmFlags::RD | VmFlags::WR));
+    assert_eq!(usage.unknown_vm_flags, ["zz", "qq"]);
+
+    let line = usage.to_string();
+    assert!(line.ends_with("VmFlags: rd wr zz qq \n"), "{}", line);
+
+    let mut merged = usage.clone();
+    merged.merge(&usage);
+    assert_eq!(merged.unknown_vm_flags, ["zz", "qq"]);
+}
+
+#[test]
+fn missing_vm_flags() {
+    let header = "00400000-004ef000 r-xp 00000000 fd:00 1835051\nRss:  4 kB\n";
+    let (next, _) = Parser::from_reader(header.as_bytes()).next().unwrap();
+    let usage = next.next().unwrap().1.unwrap();
+    assert_eq!(usage.vm_flags, None);
+    assert!(!usage.to_string().contains("VmFlags"));
+
+    let smaps = format!("{}VmFlags: \n", header);
+    let (next, _) = Parser::from_reader(smaps.as_bytes()).next().unwrap();
+    let usage = next.next().unwrap().1.unwrap();
+    assert_eq!(usage.vm_flags, Some(VmFlags::empty()));
+}
+
+#[test]
+fn vm_flags_display() {
+    assert_eq!(VmFlags::empty().to_string(), "");
+    assert_eq!((VmFlags::EX | VmFlags::RD).to_string(), "rd ex");
+    assert_eq!(VmFlags::all().to_string().split(' ').count(), 32);
+
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/data/smaps");
+    let smaps = std::fs::read_to_string(&path).unwrap();
+    let lines = smaps
+        .lines()
+        .filter_map(|line| line.strip_prefix("VmFlags:"))
+        .map(str::trim);
+    let regions = parse("smaps", true);
+    assert_eq!(lines.clone().count(), regions.len());
+    for (line, (_, usage)) in lines.zip(&regions) {
+        assert_eq!(usage.vm_flags.unwrap().to_string(), line);
+    }
+}
+
+#[test]
+fn userfaultfd_flags() {
+    for (flags, expected) in [
+        ("rd wr mr mw me ac um", VmFlags::UM),
+        ("rd wr mr mw me ac uw", VmFlags::UW),
+        ("rd wr mr mw me ac um uw", VmFlags::UM | VmFlags::UW),
+        ("rd wr mr mw me ac", VmFlags::empty()),
+    ] {
+        let smaps = format!(
+            "7f0000000000-7f0000200000 rw-p 00000000 00:00 0\nRss: 8 kB\nVmFlags: {}\n",
+            flags
+        );
+        let (next, _) = Parser::from_reader(smaps.as_bytes())
+            .strict()
+            .next()
+            .unwrap();
+        let usage = next.next().unwrap().1.unwrap();
+        let userfaultfd = usage.vm_flags.unwrap() & (VmFlags::UM | VmFlags::UW);
+        assert_eq!(userfaultfd, expected, "{}", flags);
+        assert_eq!(
+            usage.vm_flags.unwrap().is_userfaultfd(),
+            !expected.is_empty()
+        );
+    }
+}
+
+#[test]
+fn code_and_data_flags() {
+    let code = VmFlags::RD | VmFlags::EX | VmFlags::MR | VmFlags::MW | VmFlags::ME;
+    assert!(code.is_code());
+    assert!(!code.is_data());
+
+    let data = VmFlags::RD | VmFlags::WR | VmFlags::MR | VmFlags::MW | VmFlags::ME;
+    assert!(data.is_data());
+    assert!(!data.is_code());
+
+    let jit = VmFlags::RD | VmFlags::WR | VmFlags::EX;
+    assert!(!jit.is_code());
+    assert!(!jit.is_data());
+
+    assert!((data | VmFlags::SH).is_shared_mapping());
+    assert!(!data.is_shared_mapping());
+}
+
+#[test]
+fn arch_specific_flag() {
+    let smaps = "7f0000000000-7f0000001000 rw-s 00000000 00:05 10\nRss: 4 kB\nVmFlags: rd wr sh mr mw me ms ar\n";
+    let (next, _) = Parser::from_reader(smaps.as_bytes())
+        .strict()
+        .next()
+        .unwrap();
+    let flags = next.next().unwrap().1.unwrap().vm_flags.unwrap();
+    assert!(flags.contains(VmFlags::AR));
+    assert!(flags.is_arch_specific());
+    assert!(!(flags - VmFlags::AR).is_arch_specific());
+}
+
+#[test]
+fn rollup() {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/data/smaps_rollup");
+    let (next, mapping) = Parser::open(&path).unwrap().next().unwrap();
+    let mapping = mapping.unwrap();
+    assert!(mapping.device.is_anonymous());
+    assert_eq!(mapping.inode, 0);
+    assert_eq!(mapping.offset, 0);
+    assert_eq!(mapping.permissions, Permissions::P);
+    assert_eq!(mapping.path.as_deref(), Some("[rollup]"));
+
+    let (next, usage) = next.next().unwrap();
+    let usage = usage.unwrap();
+    assert_eq!(usage.size, 0);
+    assert_eq!(usage.rss, 12864 << 10);
+    assert_eq!(usage.pss_dirty, 6996 << 10);
+    assert_eq!(usage.vm_flags, None);
+    assert!(next.next().unwrap().1.is_none());
+
+    let (mapping, usage) = Parser::open(&path).unwrap().rollup().unwrap();
+    assert_eq!(mapping.start, 0x563e679e6000);
+    assert_eq!(mapping.end, 0x7ffd58ad9000);
+    assert_eq!(usage.rss, 12864 << 10);
+    assert_eq!(usage.pss, 11616 << 10);
+    assert!(matches!(
+        Parser::from_bytes(b"").rollup(),
+        Err(SmapsError::Io(_))
+    ));
+
+    let (mapping, usage) = smaps::read_rollup(std::process::id()).unwrap();
+    assert_eq!(mapping.path.as_deref(), Some("[rollup]"));
+    assert!(usage.rss > 0);
+}
+
+#[test]
+fn large_offset() {
+    let smaps = "7f0000000000-7f0000001000 r--s fffffffffffff000 fd:01 1234 /data/huge.bin\n";
+    let (_, mapping) = Parser::from_reader(smaps.as_bytes()).next().unwrap();
+    let mapping = mapping.unwrap();
+    assert_eq!(mapping.offset, 0xffff_ffff_ffff_f000);
+    assert_eq!(mapping.size(), 0x1000);
+
+    let smaps = "ffffffffff600000-ffffffffff601000 --xp 00000000 00:00 0 [vsyscall]\n";
+    let (_, mapping) = Parser::from_reader(smaps.as_bytes()).next().unwrap();
+    let mapping = mapping.unwrap();
+    assert_eq!(mapping.start, 0xffff_ffff_ff60_0000);
+    assert_eq!(mapping.size(), 0x1000);
+}
+
+#[test]
+fn contains_and_offset_of() {
+    let mapping = "7f00-7f10 r-xp 00001000 fd:01 1234 /lib.so"
+        .parse::<Mapping>()
+        .unwrap();
+    assert_eq!(mapping.size(), 0x10);
+    assert!(!mapping.contains(0x7eff));
+    assert!(mapping.contains(0x7f00));
+    assert!(mapping.contains(0x7f0f));
+    assert!(!mapping.contains(0x7f10));
+    assert_eq!(mapping.offset_of(0x7f00), Some(0x1000));
+    assert_eq!(mapping.offset_of(0x7f08), Some(0x1008));
+    assert_eq!(mapping.offset_of(0x7f10), None);
+
+    let mapping = "ffffffffffff0000-ffffffffffffffff r--p ffffffffffffffff 00:00 0"
+        .parse::<Mapping>()
+        .unwrap();
+    assert_eq!(mapping.offset_of(0xffffffffffff0000), Some(u64::MAX));
+    assert_eq!(mapping.offset_of(0xffffffffffff0001), None);
+}
+
+#[test]
+fn hex_prefix() {
+    let mapping = |header: &str| {
+        Parser::from_reader(header.as_bytes())
+            .next()
+            .map(|(_, mapping)| mapping)
+    };
+
+    let bare = mapping("7f00-7f10 r--p 00001000 fd:01 1234 /lib.so").unwrap();
+    let prefixed = mapping("0x7f00-0X7f10 r--p 0x00001000 0xfd:0x01 1234 /lib.so").unwrap();
+    assert_eq!(bare, prefixed);
+    let bare = bare.unwrap();
+    assert_eq!(
+        (bare.start, bare.end, bare.offset),
+        (0x7f00, 0x7f10, 0x1000)
+    );
+    assert_eq!((bare.device.major, bare.device.minor), (0xfd, 0x01));
+
+    assert!(matches!(mapping("0x-7f10 r--p 0 fd:01 1234"), Ok(None)));
+    assert!(matches!(mapping("+7f00-7f10 r--p 0 fd:01 1234"), Ok(None)));
+    assert!(matches!(
+        mapping("7f00-7f10 r--p 0xzz fd:01 1234"),
+        Err(SmapsError::InvalidField {
+            field: "offset",
+            ..
+        })
+    ));
+    assert!(matches!(
+        mapping("7f00-7f10 r--p 0 0x:01 1234"),
+        Err(SmapsError::InvalidField {
+            field: "device",
+            ..
+        })
+    ));
+}
+
+#[test]
+fn offset_overflow() {
+    let smaps = "7f0000000000-7f0000001000 r--s 1fffffffffffff000 fd:01 1234 /data/huge.bin\n";
+    let error = Parser::from_reader(smaps.as_bytes()).next().err();
+    assert!(matches!(error, Some(SmapsError::Overflow(_))));
+}
+
+#[test]
+fn size_overflow() {
+    let overflow = |line: &str| {
+        let result = Usage::default().parse_line(line, true);
+        matches!(result, Err(SmapsError::Overflow(_)))
+    };
+    assert!(overflow(&format!("Rss: {} kB", usize::MAX)));
+    assert!(overflow(&format!("Rss: {} kB", (usize::MAX >> 10) + 1)));
+    assert!(overflow(&format!("Rss: {}0 kB", usize::MAX)));
+    assert_eq!(overflow("Swap: 1 TB"), usize::BITS <= 40);
+
+    let mut usage = Usage::default();
+    let line = format!("Rss: {} kB", usize::MAX >> 10);
+    assert!(usage.parse_line(&line, true).unwrap());
+    assert_eq!(usage.rss, usize::MAX >> 10 << 10);
+}
+
+#[test]
+fn usage_order_independent() {
+    let smaps = include_str!("data/smaps-5.15");
+    let mut lines = smaps.lines().take(24).collect::<Vec<_>>();
+    let (_, expected) = &parse("smaps-5.15", true)[0];
+
+    // VmFlags first, then every other field in reverse
+    lines[1..].reverse();
+    let reordered = lines.join("\n");
+    let (next, _) = Parser::from_reader(reordered.as_bytes()).next().unwrap();
+    assert_eq!(next.next().unwrap().1.as_ref(), Some(expected));
+
+    // VmFlags in the middle
+    lines[1..].rotate_left(5);
+    let reordered = lines.join("\n");
+    let (next, _) = Parser::from_reader(reordered.as_bytes()).next().unwrap();
+    assert_eq!(next.next().unwrap().1.as_ref(), Some(expected));
+}
+
+#[test]
+fn guard_page() {
+    let smaps = "\
+7f0000000000-7f0000001000 ---p 00000000 00:00 0
+Size:                  4 kB
+KernelPageSize:        4 kB
+7f0000001000-7f0000801000 rw-p 00000000 00:00 0
+Size:               8192 kB
+KernelPageSize:        4 kB
+";
+    let mut parser = Parser::from_reader(smaps.as_bytes());
+    let mut guards = Vec::new();
+
+    while let (next, Some(mapping)) = parser.next().unwrap() {
+        let (next, usage) = next.next().unwrap();
+        guards.push(Region::from((mapping, usage.unwrap())).is_guard_page());
+        parser = next;
+    }
+
+    assert_eq!(guards, [true, false]);
+}
+
+#[test]
+fn region_kind() {
+    let kind = |inode: usize, path: &str| {
+        let header = format!(
+            "7f0000000000-7f0000001000 rw-p 00000000 00:00 {} {}",
+            inode, path
+        );
+        let (_, mapping) = Parser::from_reader(header.as_bytes()).next().unwrap();
+        mapping.unwrap().kind()
+    };
+
+    assert_eq!(kind(0, "[heap]"), RegionKind::Heap);
+    assert_eq!(kind(0, "[stack]"), RegionKind::Stack);
+    assert_eq!(kind(0, "[stack:1234]"), RegionKind::ThreadStack(1234));
+    assert_eq!(kind(0, "[vdso]"), RegionKind::Vdso);
+    assert_eq!(kind(0, "[vvar]"), RegionKind::Vvar);
+    assert_eq!(kind(0, "[vsyscall]"), RegionKind::Vsyscall);
+    assert_eq!(kind(0, ""), RegionKind::Anonymous);
+    assert_eq!(kind(0, "[anon:jit]"), RegionKind::Anonymous);
+    assert_eq!(kind(1835051, "/usr/bin/bash"), RegionKind::File);
+    assert_eq!(kind(1835051, ""), RegionKind::File);
+    assert_eq!(kind(0, "[uprobes]"), RegionKind::Special);
+    assert_eq!(kind(0, "[stack:tid]"), RegionKind::Special);
+    assert_eq!(kind(0, "[stack:1234]").to_string(), "stack:1234");
+    assert_eq!(kind(0, "[anon:jit]").to_string(), "anonymous");
+}
+
+#[test]
+fn device_dev_t() {
+    use std::os::unix::fs::MetadataExt;
+
+    for device in [
+        Device::new(0, 0),
+        Device::new(0xfd, 0x00),
+        Device::new(0x103, 0x1ff),
+        Device::new(0xfff_ffff, 0xffff_ffff),
+    ] {
+        assert_eq!(Device::from_dev_t(device.to_dev_t()), device);
+        #[cfg(feature = "libc")]
+        assert_eq!(device.to_dev_t(), libc::makedev(device.major, device.minor));
+    }
+
+    let dev = std::fs::metadata(env!("CARGO_MANIFEST_DIR")).unwrap().dev();
+    assert_eq!(Device::from_dev_t(dev).to_dev_t(), dev);
+
+    assert_eq!(Device::new(0xfd, 0).to_string(), "fd:00");
+    assert_eq!(Device::new(0x103, 0x1ff).to_string(), "103:1ff");
+    let (_, mapping) = Parser::from_reader(&b"00400000-004ef000 r-xp 00000000 fd:00 1835051"[..])
+        .next()
+        .unwrap();
+    assert_eq!(mapping.unwrap().device, Device::new(0xfd, 0x00));
+}
+
+#[test]
+fn device_of_path() {
+    let exe = std::fs::read_link("/proc/self/exe").unwrap();
+    let device = Device::of_path(Path::new("/proc/self/exe")).unwrap();
+    let regions = smaps::read_self().unwrap();
+    let (mapping, _) = regions
+        .iter()
+        .find(|(mapping, _)| mapping.path.as_deref().map(Path::new) == Some(exe.as_path()))
+        .unwrap();
+    assert_eq!(mapping.device, device);
+    assert!(Device::of_path(Path::new("/nonexistent")).is_err());
+}
+
+#[test]
+fn mapping_predicates() {
+    let mapping = |permissions: &str, inode: usize, path: &str| {
+        let header = format!(
+            "7f0000000000-7f0000001000 {} 00000000 00:00 {} {}",
+            permissions, inode, path
+        );
+        let (_, mapping) = Parser::from_reader(header.as_bytes()).next().unwrap();
+        mapping.unwrap()
+    };
+
+    for path in ["", "[heap]", "[stack]", "[stack:1234]", "[anon:jit]"] {
+        let mapping = mapping("rw-p", 0, path);
+        assert!(mapping.is_anonymous(), "{}", path);
+        assert!(!mapping.is_file_backed(), "{}", path);
+    }
+
+    let file = mapping("r-xp", 1835051, "/usr/bin/bash");
+    assert!(file.is_file_backed());
+    assert!(!file.is_anonymous());
+    assert!(!file.is_shared());
+
+    let vdso = mapping("r-xp", 0, "[vdso]");
+    assert!(!vdso.is_anonymous());
+    assert!(!vdso.is_file_backed());
+
+    assert!(mapping("rw-s", 1835052, "/dev/shm/buffer").is_shared());
+}
+
+#[test]
+fn io_error_classification() {
+    // EACCES and ESRCH on Linux, without depending on libc
+    const EACCES: i32 = 13;
+    const ESRCH: i32 = 3;
+
+    let error = |code| SmapsError::from(std::io::Error::from_raw_os_error(code));
+    assert!(matches!(error(EACCES), SmapsError::PermissionDenied(_)));
+    assert!(matches!(error(ESRCH), SmapsError::ProcessGone(_)));
+    assert!(matches!(
+        SmapsError::from(std::io::Error::from(std::io::ErrorKind::UnexpectedEof)),
+        SmapsError::Io(_)
+    ));
+}
+
+#[test]
+fn missing_process() {
+    let error = Parser::from_pid(u32::MAX).err();
+    assert!(matches!(error, Some(SmapsError::ProcessGone(_))));
+    assert!(matches!(
+        smaps::read(u32::MAX),
+        Err(SmapsError::ProcessGone(_))
+    ));
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn read_all() {
+    let pid = std::process::id();
+    let mut results = smaps::read_all(vec![pid, u32::MAX]);
+    results.sort_by_key(|(pid, _)| *pid);
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0].0, pid);
+    assert!(!results[0].1.as_ref().unwrap().is_empty());
+    assert!(matches!(results[1].1, Err(SmapsError::ProcessGone(_))));
+}
+
+#[cfg(feature = "procfs")]
+#[test]
+fn from_process() {
+    let process = procfs::process::Process::myself().unwrap();
+    let regions = Parser::from_process(&process)
+        .unwrap()
+        .entries()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert!(regions
+        .iter()
+        .any(|(mapping, _)| mapping.path.as_deref() == Some("[stack]")));
+}
+
+#[test]
+fn clear_refs() {
+    let pid = std::process::id();
+    smaps::reset_references(pid).unwrap();
+    smaps::reset_soft_dirty(pid).unwrap();
+    assert!(!smaps::read(pid).unwrap().is_empty());
+
+    for result in [
+        smaps::reset_references(u32::MAX),
+        smaps::reset_soft_dirty(u32::MAX),
+    ] {
+        assert_eq!(result.unwrap_err().kind(), std::io::ErrorKind::NotFound);
+    }
+}
+
+#[test]
+fn read_self() {
+    let regions = smaps::read_self().unwrap();
+    assert!(regions
+        .iter()
+        .any(|(mapping, _)| mapping.path.as_deref() == Some("[stack]")));
+    assert!(!smaps::read(std::process::id()).unwrap().is_empty());
+}
+
+#[test]
+fn corpus() {
+    let data = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/data");
+    for entry in std::fs::read_dir(data).unwrap() {
+        let name = entry.unwrap().file_name().into_string().unwrap();
+        let entries = parse(&name, false);
+        assert!(!entries.is_empty(), "{}", name);
+        for (mapping, usage) in entries {
+            assert!(mapping.start < mapping.end, "{}: {:?}", name, mapping);
+            assert!(usage.pss <= usage.rss, "{}: {:?}", name, mapping);
+        }
+    }
+
+    let entries = parse("smaps", true);
+    let find = |path: &str| {
+        entries
+            .iter()
+            .find(|(mapping, _)| mapping.path.as_deref() == Some(path))
+            .unwrap()
+    };
+
+    let (mapping, usage) = find("/tmp/My Documents/data.bin");
+    assert_eq!(mapping.basename(), Some("data.bin"));
+    assert_eq!(
+        mapping.permissions,
+        Permissions::R | Permissions::W | Permissions::S
+    );
+    assert_eq!(mapping.inode, 16179216);
+    assert_eq!(usage.private_dirty, 8 << 10);
+
+    let (mapping, usage) = find("/tmp/scratch.bin");
+    assert!(mapping.deleted);
+    assert_eq!(mapping.basename(), Some("scratch.bin"));
+    assert_eq!(mapping.start, 0x7f4c3885e000);
+    assert_eq!(usage.rss, 4 << 10);
+
+    assert_eq!(find("[heap]").0.basename(), Some("[heap]"));
+
+    let anonymous = entries
+        .iter()
+        .filter(|(mapping, _)| mapping.path.is_none())
+        .count();
+    assert!(anonymous > 0);
+}
+
+#[test]
+fn invalid_permissions() {
+    for (permissions, expected) in [
+        ("zwxp", 0),
+        ("rzxp", 1),
+        ("rwzp", 2),
+        ("rwxz", 3),
+        ("rwx", 3),
+    ] {
+        let smaps = format!("00400000-004ef000 {} 00000000 fd:00 1835051\n", permissions);
+        match Parser::from_reader(smaps.as_bytes()).next() {
+            Err(SmapsError::InvalidPermissions { position, .. }) => {
+                assert_eq!(position, expected, "{}", permissions)
+            }
+            _ => panic!("Expected invalid permissions: {}", permissions),
+        }
+    }
+
+    let smaps = "00400000-004ef000 rwxpp 00000000 fd:00 1835051\n";
+    match Parser::from_reader(smaps.as_bytes()).strict().next() {
+        Err(SmapsError::InvalidPermissions { position, .. }) => assert_eq!(position, 4),
+        _ => panic!("Expected invalid permissions in strict mode"),
+    }
+    let (_, mapping) = Parser::from_reader(smaps.as_bytes()).next().unwrap();
+    assert_eq!(
+        mapping.unwrap().permissions,
+        Permissions::R | Permissions::W | Permissions::X | Permissions::P
+    );
+
+    let smaps = "00400000-004ef000 R-XS 00000000 fd:00 1835051\n";
+    let (_, mapping) = Parser::from_reader(smaps.as_bytes()).next().unwrap();
+    assert_eq!(
+        mapping.unwrap().permissions,
+        Permissions::R | Permissions::X | Permissions::S
+    );
+}
+
+#[test]
+fn from_file() {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/data/smaps");
+    let file = std::fs::File::open(&path).unwrap();
+    let count = Parser::from_file(file).entries().count();
+    assert_eq!(count, Parser::open(&path).unwrap().entries().count());
+    assert_eq!(count, 47);
+}
+
+#[test]
+fn mapping_display_round_trip() {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/data/smaps");
+    let mut count = 0;
+    for entry in Parser::open(&path).unwrap().keep_raw() {
+        let (mapping, _) = entry.unwrap();
+        let raw = mapping.raw.clone().unwrap();
+        assert_eq!(mapping.to_string(), raw.trim_end());
+
+        let parsed = raw.parse::<Mapping>().unwrap();
+        assert_eq!(
+            Mapping {
+                raw: None,
+                ..mapping
+            },
+            parsed
+        );
+        count += 1;
+    }
+    assert_eq!(count, 47);
+
+    let anonymous = "7f0000000000-7f0000001000 ---p 00000000 00:00 0";
+    assert_eq!(anonymous.parse::<Mapping>().unwrap().to_string(), anonymous);
+    assert!("Rss: 4 kB".parse::<Mapping>().is_err());
+}
+
+#[test]
+fn smaps_display_round_trip() {
+    for name in ["smaps", "smaps-5.15", "smaps-4.19", "smaps-3.10"] {
+        let regions = parse(name, true);
+        let mut rendered = String::new();
+        for (mapping, usage) in &regions {
+            writeln!(rendered, "{}", mapping).unwrap();
+            write!(rendered, "{}", usage).unwrap();
+        }
+
+        let reparsed = Parser::from_reader(rendered.as_bytes())
+            .strict()
+            .entries()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(reparsed, regions, "{}", name);
+    }
+}
+
+#[cfg(feature = "libc")]
+#[test]
+fn permissions_libc_flags() {
+    let permissions = |text: &str| text.parse::<Permissions>().unwrap();
+
+    let code = permissions("r-xp");
+    assert_eq!(code.to_prot_flags(), libc::PROT_READ | libc::PROT_EXEC);
+    assert_eq!(code.to_map_flags(), libc::MAP_PRIVATE);
+
+    let shared = permissions("rw-s");
+    assert_eq!(shared.to_prot_flags(), libc::PROT_READ | libc::PROT_WRITE);
+    assert_eq!(shared.to_map_flags(), libc::MAP_SHARED);
+
+    assert_eq!(permissions("---p").to_prot_flags(), libc::PROT_NONE);
+    assert_eq!(Permissions::R.to_map_flags(), 0);
+}
+
+#[test]
+fn permissions_wx() {
+    let permissions = |text: &str| text.parse::<Permissions>().unwrap();
+
+    assert!(permissions("rwxp").is_wx());
+    assert!(!permissions("r-xp").is_wx());
+    assert!(!permissions("rw-p").is_wx());
+
+    assert_eq!(
+        permissions("r-xp").difference_description(permissions("r-xp")),
+        ""
+    );
+    assert_eq!(
+        permissions("rwxp").difference_description(permissions("r-xp")),
+        "unexpectedly writable"
+    );
+    assert_eq!(
+        permissions("rwxs").difference_description(permissions("r--p")),
+        "unexpectedly writable+executable+shared, unexpectedly not private"
+    );
+}
+
+#[test]
+fn permissions_display_round_trip() {
+    for read in ["r", "-"] {
+        for write in ["w", "-"] {
+            for execute in ["x", "-"] {
+                for shared in ["s", "p"] {
+                    let permissions = [read, write, execute, shared].concat();
+                    let parsed = permissions.parse::<Permissions>().unwrap();
+                    assert_eq!(parsed.to_string(), permissions);
+                }
+            }
+        }
+    }
+
+    assert_eq!("R-XS".parse::<Permissions>().unwrap().to_string(), "r-xs");
+    assert!("rwxpp".parse::<Permissions>().is_err());
+    assert!("rwx".parse::<Permissions>().is_err());
+}
+
+#[test]
+fn permission_bits_round_trip() {
+    for bits in 0..=u8::MAX {
+        match Permissions::try_from(bits) {
+            Ok(permissions) => {
+                assert_eq!(permissions.bits(), bits);
+                assert_eq!(
+                    Permissions::from_bits(permissions.bits()),
+                    Some(permissions)
+                );
+                assert_eq!(
+                    permissions.to_string().parse::<Permissions>().unwrap(),
+                    permissions
+                );
+            }
+            Err(SmapsError::InvalidPermissionBits(invalid)) => {
+                assert_eq!(invalid, bits);
+                assert!(Permissions::from_bits(bits).is_none_or(|permissions| {
+                    permissions.contains(Permissions::S) == permissions.contains(Permissions::P)
+                }));
+            }
+            Err(error) => panic!("Unexpected error: {}", error),
+        }
+    }
+
+    assert!(Permissions::try_from((Permissions::R | Permissions::P).bits()).is_ok());
+    assert!(Permissions::try_from((Permissions::S | Permissions::P).bits()).is_err());
+    assert!(Permissions::try_from(1 << 5).is_err());
+    assert!(Permissions::try_from((Permissions::R | Permissions::W).bits()).is_err());
+    assert!(Permissions::try_from(0).is_err());
+}
+
+/// Repeats one smaps entry `remaining` times without materializing the stream.
+struct Repeat {
+    entry: &'static [u8],
+    position: usize,
+    remaining: usize,
+}
+
+impl Read for Repeat {
+    fn read(&mut self, buffer: &mut [u8]) -> std::io::Result<usize> {
+        if self.position == self.entry.len() {
+            if self.remaining == 0 {
+                return Ok(0);
+            }
+            self.position = 0;
+            self.remaining -= 1;
+        }
+
+        let read = (&self.entry[self.position..]).read(buffer)?;
+        self.position += read;
+        Ok(read)
+    }
+}
+
+#[test]
+fn entries_stream() {
+    fn assert_send<T: Send>(_: &T) {}
+
+    // Two mappings per repetition
+    let entry = include_bytes!("data/smaps-4.19");
+    let count = 50_000;
+    let reader = Repeat {
+        entry,
+        position: entry.len(),
+        remaining: count,
+    };
+
+    let entries = Parser::from_reader(BufReader::new(reader)).entries();
+    assert_send(&entries);
+
+    let mut total = 0;
+    for entry in entries {
+        total += entry.unwrap().1.rss;
+    }
+
+    assert_eq!(total, (count * (600 + 92)) << 10);
+}
+
+#[test]
+fn entries_edge_cases() {
+    assert_eq!(Parser::from_reader(&b""[..]).entries().count(), 0);
+
+    let smaps = "\
+00400000-004ef000 r-xp 00000000 fd:00 1835051 /usr/bin/bash
+Rss:                 600 kB
+7f0000000000-7f0000001000 ---p 00000000 00:00 0
+";
+    let mut regions = Vec::new();
+    for entry in Parser::from_reader(smaps.as_bytes()) {
+        regions.push(entry.unwrap());
+    }
+    assert_eq!(regions.len(), 2);
+    assert_eq!(regions[0].1.rss, 600 << 10);
+    assert_eq!(regions[1].0.start, 0x7f0000000000);
+    assert_eq!(regions[1].1, Usage::default());
+}
+
+#[test]
+fn filter_permissions() {
+    // The data mapping's usage block would fail to parse in strict mode
+    let smaps = "\
+00400000-004ef000 r-xp 00000000 fd:00 1835051 /usr/bin/bash
+Rss:                 600 kB
+006ef000-006f0000 rw-p 000ef000 fd:00 1835051 /usr/bin/bash
+Rss:                 four kB
+7f0000000000-7f0000001000 r-xp 00000000 fd:00 1835052 /usr/lib/libc.so.6
+Rss:                 92 kB
+";
+    let regions = Parser::from_reader(smaps.as_bytes())
+        .strict()
+        .entries()
+        .filter_permissions(Permissions::R | Permissions::X)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(regions.len(), 2);
+    assert_eq!(regions[0].1.rss, 600 << 10);
+    assert_eq!(regions[1].0.path.as_deref(), Some("/usr/lib/libc.so.6"));
+    assert_eq!(regions[1].1.rss, 92 << 10);
+
+    let code = Parser::open(&Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/data/smaps"))
+        .unwrap()
+        .entries()
+        .filter_permissions(Permissions::X)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    let expected = parse("smaps", false)
+        .into_iter()
+        .filter(|(mapping, _)| mapping.permissions.contains(Permissions::X))
+        .collect::<Vec<_>>();
+    assert!(!code.is_empty());
+    assert_eq!(code, expected);
+}
+
+#[test]
+fn mappings_only() {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/data/smaps");
+    let mappings = Parser::open(&path)
+        .unwrap()
+        .strict()
+        .mappings_only()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    let expected = parse("smaps", true)
+        .into_iter()
+        .map(|(mapping, _)| mapping)
+        .collect::<Vec<_>>();
+    assert_eq!(mappings, expected);
+
+    // Usage blocks aren't parsed, so even strict mode doesn't check them
+    let smaps = "\
+00400000-004ef000 r-xp 00000000 fd:00 1835051 /usr/bin/bash
+Rss:                 four kB
+7f0000000000-7f0000001000 ---p 00000000 00:00 0
+";
+    let mappings = Parser::from_reader(smaps.as_bytes())
+        .strict()
+        .mappings_only()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(mappings.len(), 2);
+    assert_eq!(mappings[1].start, 0x7f0000000000);
+}
+
+#[test]
+fn maps() {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/data/maps");
+    let mappings = Parser::open_maps(&path)
+        .unwrap()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    let expected = parse("smaps", true)
+        .into_iter()
+        .map(|(mapping, _)| mapping)
+        .collect::<Vec<_>>();
+    assert_eq!(mappings, expected);
+
+    let kinds = mappings.iter().map(Mapping::kind).collect::<Vec<_>>();
+    assert!(kinds.contains(&RegionKind::Heap));
+    assert!(kinds.contains(&RegionKind::Stack));
+    assert!(kinds.contains(&RegionKind::File));
+
+    let own = smaps::read_maps(std::process::id()).unwrap();
+    assert!(own
+        .iter()
+        .any(|mapping| mapping.path.as_deref() == Some("[stack]")));
+    assert!(matches!(
+        smaps::read_maps(u32::MAX),
+        Err(SmapsError::ProcessGone(_))
+    ));
+}
+
+#[test]
+fn filter_path() {
+    let libc = parse("smaps", false)
+        .into_iter()
+        .filter(|(mapping, _)| {
+            mapping
+                .path
+                .as_deref()
+                .is_some_and(|path| path.ends_with("libc.so.6"))
+        })
+        .collect::<Vec<_>>();
+    assert!(!libc.is_empty());
+
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/data/smaps");
+    let filtered = Parser::open(&path)
+        .unwrap()
+        .entries()
+        .filter_path(|path| path.ends_with("libc.so.6"))
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(filtered, libc);
+
+    let code = Parser::open(&path)
+        .unwrap()
+        .entries()
+        .filter_path(|_| true)
+        .filter_permissions(Permissions::X)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert!(
+        code.iter()
+            .all(|(mapping, _)| mapping.path.is_some()
+                && mapping.permissions.contains(Permissions::X))
+    );
+}
+
+#[test]
+fn from_bytes() {
+    const SMAPS: &str = include_str!("data/smaps-4.19");
+
+    let regions = Parser::from_bytes(SMAPS.as_bytes())
+        .strict()
+        .entries()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(regions, parse("smaps-4.19", true));
+    assert_eq!(regions.len(), 2);
+}
+
+#[test]
+fn parse_lines_without_parser() {
+    let smaps = include_str!("data/smaps-5.15");
+    let mut regions = Vec::<(Mapping, Usage)>::new();
+    for line in smaps.lines() {
+        let usage_line = match regions.last_mut() {
+            Some((_, usage)) => usage.parse_line(line, true).unwrap(),
+            None => false,
+        };
+        if !usage_line {
+            regions.push((line.parse().unwrap(), Usage::default()));
+        }
+    }
+
+    assert_eq!(regions, parse("smaps-5.15", true));
+
+    let mut usage = Usage::default();
+    assert!(usage.parse_line("Rss:  4 kB", false).unwrap());
+    assert!(usage.parse_line("FutureField:  4 kB", false).unwrap());
+    assert!(usage.parse_line("VmFlags: rd wr", false).unwrap());
+    assert!(!usage
+        .parse_line("7f00-7f10 r--p 00000000 00:00 0", false)
+        .unwrap());
+    assert_eq!(usage.rss, 4 << 10);
+    assert_eq!(usage.vm_flags, Some(VmFlags::RD | VmFlags::WR));
+}
+
+#[test]
+fn truncated_header() {
+    for (header, expected) in [
+        ("00400000-004ef000", "permissions"),
+        ("00400000-004ef000 r-xp", "offset"),
+        ("00400000-004ef000 r-xp 00000000", "device"),
+        ("00400000-004ef000 r-xp 00000000 fd:00", "inode"),
+    ] {
+        match Parser::from_reader(header.as_bytes()).next() {
+            Err(SmapsError::MissingField { field, .. }) => assert_eq!(field, expected),
+            _ => panic!("Expected missing {}: {}", expected, header),
+        }
+    }
+
+    let header = "00400000-004ef000 r-xp 00000000 fd 1835051";
+    let error = Parser::from_reader(header.as_bytes()).next().err();
+    assert!(matches!(
+        error,
+        Some(SmapsError::InvalidField {
+            field: "device",
+            ..
+        })
+    ));
+}
+
+#[test]
+fn inverted_range() {
+    let header = "00002000-00001000 r--p 00000000 00:00 0";
+    let error = Parser::from_reader(header.as_bytes()).next().err();
+    assert!(matches!(
+        error,
+        Some(SmapsError::InvalidField { field: "end", .. })
+    ));
+    assert!(header.parse::<Mapping>().is_err());
+
+    let smaps = format!("{}\n00001000-00002000 r--p 00000000 00:00 0\n", header);
+    let regions = Parser::from_reader(smaps.as_bytes())
+        .tolerant()
+        .entries()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(regions.len(), 1);
+    assert_eq!(regions[0].0.size(), 0x1000);
+}
+
+#[test]
+fn path_whitespace() {
+    let path = |header: &str| {
+        let (_, mapping) = Parser::from_reader(header.as_bytes()).next().unwrap();
+        mapping.unwrap().path
+    };
+
+    // Padded to the kernel's path column
+    let padded = "563e679e6000-563e679e7000 r--p 00000000 fe:00 18479";
+    let padded = format!("{:<72} ", padded);
+    assert_eq!(
+        path(&format!("{}/bin/sh  ", padded)).as_deref(),
+        Some("/bin/sh")
+    );
+    assert_eq!(
+        path(&format!("{} leading", padded)).as_deref(),
+        Some(" leading")
+    );
+    assert_eq!(path(&padded), None);
+
+    // Header already longer than the path column
+    let long = "ffffffffff600000-ffffffffff601000 r--p 0000000000000000 fe:00 1234567890123";
+    assert_eq!(
+        path(&format!("{} /bin/sh", long)).as_deref(),
+        Some("/bin/sh")
+    );
+    assert_eq!(
+        path(&format!("{}  leading", long)).as_deref(),
+        Some(" leading")
+    );
+
+    // Unpadded, as written by other tools
+    let short = "00400000-004ef000 r-xp 00000000 fd:00 1835051";
+    assert_eq!(
+        path(&format!("{}   /bin/sh", short)).as_deref(),
+        Some("/bin/sh")
+    );
+
+    // Spaces within the path
+    for header in [padded, format!("{} ", long), format!("{} ", short)] {
+        assert_eq!(
+            path(&format!("{}/home/me/My Documents/lib.so", header)).as_deref(),
+            Some("/home/me/My Documents/lib.so")
+        );
+        assert_eq!(
+            path(&format!("{}[stack]", header)).as_deref(),
+            Some("[stack]")
+        );
+    }
+    assert_eq!(path(short), None);
+
+    // Deleted files
+    let deleted = |header: &str| {
+        let (_, mapping) = Parser::from_reader(header.as_bytes()).next().unwrap();
+        let mapping = mapping.unwrap();
+        (mapping.path, mapping.deleted)
+    };
+    assert_eq!(
+        deleted(&format!("{} /tmp/a b (deleted)", short)),
+        (Some("/tmp/a b".to_owned()), true)
+    );
+    assert_eq!(
+        deleted(&format!("{} /tmp/a b", short)),
+        (Some("/tmp/a b".to_owned()), false)
+    );
+}
@@ -0,0 +1,47 @@
+#![cfg(feature = "tokio")]
+
+use std::path::Path;
+
+use futures_util::StreamExt;
+use smaps::Parser;
+use smaps::SmapsError;
+
+fn block_on<F: std::future::Future>(future: F) -> F::Output {
+    tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap()
+        .block_on(future)
+}
+
+#[test]
+fn stream_entries() {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/data/smaps");
+    let expected = Parser::open(&path)
+        .unwrap()
+        .entries()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+
+    let regions = block_on(async {
+        let mut stream = Parser::snapshot_async(&path).await.unwrap().into_stream();
+        let mut regions = Vec::new();
+        while let Some(entry) = stream.next().await {
+            regions.push(entry.unwrap());
+        }
+        regions
+    });
+    assert_eq!(regions, expected);
+}
+
+#[test]
+fn stream_pid() {
+    block_on(async {
+        let parser = Parser::from_pid_async(std::process::id()).await.unwrap();
+        let regions = parser.into_stream().collect::<Vec<_>>().await;
+        assert!(!regions.is_empty());
+        assert!(regions.iter().all(Result::is_ok));
+
+        let error = Parser::from_pid_async(u32::MAX).await.err();
+        assert!(matches!(error, Some(SmapsError::ProcessGone(_))));
+    });
+}
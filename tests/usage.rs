@@ -0,0 +1,730 @@
+use std::collections::BinaryHeap;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::path::Path;
+
+use smaps::by_field;
+use smaps::categorize;
+use smaps::changes;
+use smaps::Category;
+use smaps::Change;
+use smaps::DominantKind;
+use smaps::Inconsistency;
+use smaps::Mapping;
+use smaps::MatchBy;
+use smaps::Parser;
+use smaps::Permissions;
+use smaps::Ranked;
+use smaps::RegionKind;
+use smaps::Snapshot;
+use smaps::Summary;
+use smaps::Usage;
+use smaps::UsageField;
+use smaps::VmFlags;
+
+fn sample() -> Vec<(Mapping, Usage)> {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/data/smaps");
+    Parser::open(&path)
+        .unwrap()
+        .keep_raw()
+        .entries()
+        .collect::<Result<_, _>>()
+        .unwrap()
+}
+
+#[test]
+fn rss_breakdown_sums_to_rss() {
+    let entries = sample();
+    assert!(!entries.is_empty());
+
+    for (mapping, usage) in entries {
+        assert_eq!(usage.shared_hugetlb + usage.private_hugetlb, 0);
+        assert_eq!(usage.rss_breakdown().total, usage.rss, "{:?}", mapping);
+    }
+}
+
+#[test]
+fn sample_is_consistent() {
+    for (mapping, usage) in sample() {
+        assert_eq!(usage.validate(), Ok(()), "{:?}", mapping);
+        assert!(mapping.is_page_aligned_for(&usage), "{:?}", mapping);
+        assert!(mapping.is_page_aligned(4 << 10), "{:?}", mapping);
+        assert!(!mapping.is_page_aligned(0), "{:?}", mapping);
+    }
+}
+
+#[test]
+fn validate_inconsistent() {
+    let consistent = Usage {
+        size: 16 << 10,
+        rss: 8 << 10,
+        pss: 8 << 10,
+        pss_dirty: 4 << 10,
+        private_clean: 4 << 10,
+        private_dirty: 4 << 10,
+        referenced: 8 << 10,
+        anonymous: 8 << 10,
+        swap: 4 << 10,
+        swap_pss: 4 << 10,
+        ..Usage::default()
+    };
+    assert_eq!(consistent.validate(), Ok(()));
+
+    let break_with = |update: fn(&mut Usage)| {
+        let mut usage = consistent.clone();
+        update(&mut usage);
+        usage.validate()
+    };
+    assert_eq!(
+        break_with(|usage| usage.size = 4 << 10),
+        Err(vec![Inconsistency::RssExceedsSize])
+    );
+    assert_eq!(
+        break_with(|usage| usage.pss = 12 << 10),
+        Err(vec![Inconsistency::PssExceedsRss])
+    );
+    assert_eq!(
+        break_with(|usage| usage.pss_dirty = 12 << 10),
+        Err(vec![Inconsistency::PssDirtyExceedsPss])
+    );
+    assert_eq!(
+        break_with(|usage| usage.referenced = 12 << 10),
+        Err(vec![Inconsistency::ReferencedExceedsRss])
+    );
+    assert_eq!(
+        break_with(|usage| usage.anonymous = 12 << 10),
+        Err(vec![Inconsistency::AnonymousExceedsRss])
+    );
+    assert_eq!(
+        break_with(|usage| usage.lazy_free = 12 << 10),
+        Err(vec![Inconsistency::LazyFreeExceedsAnonymous])
+    );
+    assert_eq!(
+        break_with(|usage| usage.anon_huge_pages = 12 << 10),
+        Err(vec![Inconsistency::AnonHugePagesExceedsAnonymous])
+    );
+    assert_eq!(
+        break_with(|usage| usage.swap_pss = 8 << 10),
+        Err(vec![Inconsistency::SwapPssExceedsSwap])
+    );
+    assert_eq!(
+        break_with(|usage| usage.private_dirty = 8 << 10),
+        Err(vec![Inconsistency::RssBreakdownMismatch])
+    );
+
+    // The breakdown is only checked without hugetlb pages
+    assert_eq!(
+        break_with(|usage| {
+            usage.private_dirty = 8 << 10;
+            usage.private_hugetlb = 2 << 20;
+        }),
+        Ok(())
+    );
+    assert_eq!(
+        break_with(|usage| usage.rss = 32 << 10),
+        Err(vec![
+            Inconsistency::RssExceedsSize,
+            Inconsistency::RssBreakdownMismatch,
+        ])
+    );
+}
+
+#[test]
+fn display_reparses() {
+    for (mapping, usage) in sample() {
+        let text = format!("{}\n{}", mapping.raw.as_ref().unwrap(), usage);
+        let parser = Parser::from_reader(text.as_bytes());
+        let (next, reparsed) = parser.next().unwrap();
+        assert_eq!(reparsed.map(|mapping| mapping.start), Some(mapping.start));
+        assert_eq!(next.next().unwrap().1, Some(usage));
+    }
+}
+
+#[test]
+fn categorize_sample() {
+    let entries = sample();
+    let categories = categorize(&entries);
+
+    for category in [
+        Category::Heap,
+        Category::Stack,
+        Category::Code,
+        Category::FileData,
+        Category::Anonymous,
+        Category::Special,
+    ] {
+        assert!(categories.contains_key(&category), "{:?}", category);
+    }
+
+    let rss = entries.iter().map(|(_, usage)| usage.rss).sum::<usize>();
+    let pss = entries.iter().map(|(_, usage)| usage.pss).sum::<usize>();
+    assert_eq!(
+        categories.values().map(|usage| usage.rss).sum::<usize>(),
+        rss
+    );
+    assert_eq!(
+        categories.values().map(|usage| usage.pss).sum::<usize>(),
+        pss
+    );
+    assert!(categories[&Category::Code].rss > 0);
+}
+
+#[test]
+fn category_agrees_with_kind() {
+    for (mapping, _) in sample() {
+        let expected = match mapping.kind() {
+            RegionKind::Heap => Category::Heap,
+            RegionKind::Stack | RegionKind::ThreadStack(_) => Category::Stack,
+            RegionKind::Anonymous => Category::Anonymous,
+            RegionKind::File if mapping.permissions.contains(Permissions::X) => Category::Code,
+            RegionKind::File => Category::FileData,
+            _ => Category::Special,
+        };
+        assert_eq!(mapping.category(), expected, "{:?}", mapping);
+    }
+
+    let category = |header: &str| {
+        let (_, mapping) = Parser::from_reader(header.as_bytes()).next().unwrap();
+        let mapping = mapping.unwrap();
+        (mapping.kind(), mapping.category())
+    };
+    assert_eq!(
+        category("7f00-7f10 r-xp 00000000 fd:01 1234 /usr/lib/libc.so.6"),
+        (RegionKind::File, Category::Code)
+    );
+    assert_eq!(
+        category("7f00-7f10 rw-p 00000000 fd:01 1234"),
+        (RegionKind::File, Category::FileData)
+    );
+    assert_eq!(
+        category("7f00-7f10 rw-p 00000000 00:00 0 relative/path"),
+        (RegionKind::File, Category::FileData)
+    );
+    assert_eq!(
+        category("7f00-7f10 rw-p 00000000 00:00 0"),
+        (RegionKind::Anonymous, Category::Anonymous)
+    );
+    assert_eq!(
+        category("7f00-7f10 rw-p 00000000 00:00 0 [anon:arena]"),
+        (RegionKind::Anonymous, Category::Anonymous)
+    );
+    assert_eq!(
+        category("7f00-7f10 rw-p 00000000 00:00 0 [stack:1234]"),
+        (RegionKind::ThreadStack(1234), Category::Stack)
+    );
+    assert_eq!(
+        category("7f00-7f10 r-xp 00000000 00:00 0 [vdso]"),
+        (RegionKind::Vdso, Category::Special)
+    );
+}
+
+#[test]
+fn top_paths_by_pss() {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/data/smaps");
+    let top = smaps::top_paths(Parser::open(&path).unwrap(), UsageField::Pss, 3).unwrap();
+
+    let snapshot = sample().into_iter().collect::<Snapshot>();
+    let mut expected = snapshot
+        .by_path()
+        .into_iter()
+        .map(|(path, regions)| (regions.iter().map(|(_, usage)| usage.pss).sum(), path))
+        .collect::<Vec<(usize, _)>>();
+    expected.sort_by(|left, right| right.0.cmp(&left.0));
+
+    assert_eq!(top.len(), 3);
+    for ((path, usage), (pss, _)) in top.iter().zip(&expected) {
+        assert_eq!(usage.pss, *pss, "{}", path);
+    }
+
+    let all = smaps::top_paths(Parser::open(&path).unwrap(), UsageField::Pss, usize::MAX);
+    assert_eq!(all.unwrap().len(), expected.len());
+    let none = smaps::top_paths(Parser::open(&path).unwrap(), UsageField::Pss, 0);
+    assert!(none.unwrap().is_empty());
+}
+
+#[test]
+fn summarize_self() {
+    let summary = smaps::summarize_pid(std::process::id()).unwrap();
+    assert!(summary.count > 0);
+    assert!(summary.uss <= summary.rss);
+    assert!(summary.pss <= summary.rss);
+    assert!(summary.rss <= summary.size);
+}
+
+#[test]
+fn summary_from_entries() {
+    let entries = sample();
+    let summary = Summary::from_entries(entries.clone());
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/data/smaps");
+    assert_eq!(
+        summary,
+        smaps::summarize(Parser::open(&path).unwrap()).unwrap()
+    );
+
+    assert_eq!(summary.count, entries.len());
+    assert_eq!(summary.pss, Snapshot::from(entries).total_pss());
+    assert_eq!(
+        summary
+            .by_kind
+            .values()
+            .map(|usage| usage.pss)
+            .sum::<usize>(),
+        summary.pss
+    );
+    assert!(summary.by_kind[&RegionKind::Heap].pss > 0);
+    assert!(summary.by_kind[&RegionKind::File].rss > 0);
+}
+
+#[test]
+fn unique_set_size() {
+    let entries = sample();
+    for (mapping, usage) in &entries {
+        assert_eq!(
+            usage.uss(),
+            usage.private_clean + usage.private_dirty,
+            "{:?}",
+            mapping
+        );
+        assert!(usage.uss() <= usage.rss, "{:?}", mapping);
+    }
+
+    let snapshot = Snapshot::from(entries.clone());
+    let uss = entries.iter().map(|(_, usage)| usage.uss()).sum::<usize>();
+    assert!(uss > 0);
+    assert_eq!(snapshot.total_uss(), uss);
+    assert_eq!(snapshot.total().uss(), uss);
+}
+
+#[test]
+fn sort_regions() {
+    let entries = sample();
+
+    let mut sorted = entries.iter().rev().cloned().collect::<Vec<_>>();
+    sorted.sort_by(|left, right| left.0.cmp(&right.0));
+    assert_eq!(sorted, entries);
+
+    sorted.sort_by(smaps::by_pss);
+    assert!(sorted.windows(2).all(|pair| pair[0].1.pss >= pair[1].1.pss));
+
+    sorted.sort_by(smaps::by_rss);
+    assert!(sorted.windows(2).all(|pair| pair[0].1.rss >= pair[1].1.rss));
+    assert_eq!(sorted.len(), entries.len());
+}
+
+#[test]
+fn binary_search_by_address() {
+    let mut entries = sample();
+    entries.reverse();
+    entries.sort_by_key(|(mapping, _)| mapping.sort_key());
+    assert!(entries.windows(2).all(|pair| pair[0].0 <= pair[1].0));
+
+    let find = |address: u64| {
+        let index = entries.partition_point(|(mapping, _)| mapping.end <= address);
+        entries
+            .get(index)
+            .filter(|(mapping, _)| mapping.contains(address))
+    };
+    for (mapping, _) in &entries {
+        assert_eq!(find(mapping.start).unwrap().0, *mapping);
+        assert_eq!(find(mapping.end - 1).unwrap().0, *mapping);
+    }
+    assert!(find(0).is_none());
+}
+
+#[test]
+fn find_overlaps() {
+    let mut entries = sample();
+    assert!(Snapshot::from(entries.clone()).find_overlaps().is_empty());
+
+    let (mut mapping, usage) = entries[1].clone();
+    mapping.start -= 1 << 12;
+    mapping.end += 1 << 12;
+    entries.push((mapping, usage));
+
+    let last = entries.len() - 1;
+    assert_eq!(
+        Snapshot::from(entries).find_overlaps(),
+        vec![(0, last), (1, last), (2, last)],
+    );
+}
+
+#[test]
+fn get_field_by_key() {
+    for (_, usage) in sample() {
+        assert_eq!(usage.get_field("Pss"), Some(usage.pss));
+        assert_eq!(usage.get_field("Private_Dirty"), Some(usage.private_dirty));
+        assert_eq!(usage.get_field("MMUPageSize"), Some(usage.mmu_page_size));
+        assert_eq!(usage.get_field("KSM"), Some(usage.ksm));
+        assert_eq!(usage.get_field("Locked"), Some(usage.locked));
+        assert_eq!(UsageField::Size.get(&usage), usage.size);
+        assert_eq!(UsageField::SwapPss.get(&usage), usage.swap_pss);
+        assert_eq!(UsageField::MmuPageSize.key(), "MMUPageSize");
+        assert_eq!(usage.get_field("ProtectionKey"), usage.protection_key);
+        assert_eq!(usage.get_field("VmFlags"), None);
+    }
+}
+
+#[test]
+fn sum_owned() {
+    let entries = sample();
+    let borrowed = entries.iter().map(|(_, usage)| usage).sum::<Usage>();
+    let owned = entries.into_iter().map(|(_, usage)| usage).sum::<Usage>();
+    assert_eq!(owned, borrowed);
+
+    let left = Usage {
+        rss: 4 << 10,
+        thp_eligible: Some(false),
+        ..Usage::default()
+    };
+    let right = Usage {
+        rss: 8 << 10,
+        thp_eligible: Some(true),
+        ..Usage::default()
+    };
+    let mut total = left.clone() + right.clone();
+    assert_eq!(total.rss, 12 << 10);
+    assert_eq!(total.thp_eligible, Some(true));
+    total += left;
+    assert_eq!(total.rss, 16 << 10);
+
+    let key = |protection_key| Usage {
+        protection_key,
+        ..Usage::default()
+    };
+    assert_eq!((key(Some(1)) + key(Some(2))).protection_key, None);
+    assert_eq!((key(Some(1)) + key(Some(1))).protection_key, Some(1));
+    assert_eq!((key(None) + key(Some(1))).protection_key, Some(1));
+    assert_eq!(
+        [key(Some(0)), key(Some(0))]
+            .into_iter()
+            .sum::<Usage>()
+            .protection_key,
+        Some(0)
+    );
+
+    let keys = [key(Some(1)), key(Some(2)), key(Some(1))];
+    for order in [
+        [0, 1, 2],
+        [0, 2, 1],
+        [1, 0, 2],
+        [1, 2, 0],
+        [2, 0, 1],
+        [2, 1, 0],
+    ] {
+        let total = order
+            .map(|index| keys[index].clone())
+            .into_iter()
+            .sum::<Usage>();
+        assert_eq!(total.protection_key, None, "{:?}", order);
+        assert!(total.mixed_protection_keys, "{:?}", order);
+    }
+    let total = key(Some(1)) + key(Some(1)) + key(None);
+    assert_eq!(total.protection_key, Some(1));
+    assert!(!total.mixed_protection_keys);
+}
+
+#[test]
+fn total_matches_rollup() {
+    let total = sample().into_iter().collect::<Snapshot>().total();
+
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/data/smaps_rollup");
+    let (next, _) = Parser::open(&path).unwrap().next().unwrap();
+    let rollup = next.next().unwrap().1.unwrap();
+
+    // The kernel accumulates PSS at a finer granularity for the rollup
+    assert!(total.pss.abs_diff(rollup.pss) <= rollup.pss / 100);
+    assert_eq!(total.rss, rollup.rss);
+    assert_eq!(total.rss_breakdown(), rollup.rss_breakdown());
+    assert_eq!(total.anonymous, rollup.anonymous);
+    assert_eq!(total.swap, rollup.swap);
+}
+
+#[test]
+fn rebase() {
+    let entries = sample();
+    let base = entries[0].0.start;
+    let mut snapshot = Snapshot::from(entries.clone());
+    snapshot.rebase();
+
+    assert_eq!(snapshot[0].0.start, 0);
+    for ((rebased, _), (mapping, _)) in snapshot.iter().zip(&entries) {
+        assert_eq!(rebased.start + base, mapping.start);
+        assert_eq!(rebased.end - rebased.start, mapping.end - mapping.start);
+    }
+}
+
+#[test]
+fn normalize_page_size() {
+    for (_, usage) in sample() {
+        assert_eq!(usage.pages(|usage| usage.rss, 4 << 10), usage.rss >> 12);
+
+        let normalized = usage.normalize(64 << 10);
+        assert_eq!(normalized.kernel_page_size, 64 << 10);
+        assert_eq!(normalized.rss % (64 << 10), 0);
+        assert!(normalized.rss >= usage.rss);
+        assert!(normalized.rss - usage.rss < 64 << 10);
+
+        assert_eq!(
+            usage.pages(|usage| usage.rss, 0),
+            usage.pages(|usage| usage.rss, smaps::DEFAULT_PAGE_SIZE)
+        );
+        assert_eq!(
+            usage.normalize(0),
+            usage.normalize(smaps::DEFAULT_PAGE_SIZE)
+        );
+    }
+}
+
+#[test]
+fn dominant_kind() {
+    let usage = |private_dirty, swap, shared_clean| Usage {
+        private_dirty,
+        swap,
+        shared_clean,
+        ..Usage::default()
+    };
+
+    assert_eq!(usage(0, 0, 0).dominant_kind(), DominantKind::Empty);
+    assert_eq!(usage(8, 4, 4).dominant_kind(), DominantKind::PrivateDirty);
+    assert_eq!(usage(4, 8, 4).dominant_kind(), DominantKind::Swap);
+    assert_eq!(usage(4, 4, 8).dominant_kind(), DominantKind::SharedClean);
+    assert_eq!(usage(4, 4, 4).dominant_kind(), DominantKind::PrivateDirty);
+    assert_eq!(usage(0, 4, 4).dominant_kind(), DominantKind::Swap);
+
+    let entries = sample();
+    let (_, heap) = entries
+        .iter()
+        .find(|(mapping, _)| mapping.path.as_deref() == Some("[heap]"))
+        .unwrap();
+    assert_eq!(heap.dominant_kind(), DominantKind::PrivateDirty);
+}
+
+#[test]
+fn filter_writable() {
+    let mut snapshot = Snapshot::from(sample());
+    let writable = |(mapping, _): &(Mapping, Usage)| mapping.permissions.contains(Permissions::W);
+
+    let filtered = snapshot.filter(writable);
+    assert!(!filtered.is_empty());
+    assert!(filtered.len() < snapshot.len());
+    assert!(filtered.iter().all(writable));
+
+    snapshot.retain(writable);
+    assert_eq!(snapshot, filtered);
+}
+
+#[test]
+fn sort_by_field() {
+    let mut usages = sample()
+        .into_iter()
+        .map(|(_, usage)| usage)
+        .collect::<Vec<_>>();
+
+    for field in [UsageField::Pss, UsageField::PrivateDirty, UsageField::Swap] {
+        usages.sort_by(by_field(field));
+        assert!(usages
+            .windows(2)
+            .all(|pair| field.get(&pair[0]) <= field.get(&pair[1])));
+    }
+
+    for (_, usage) in sample() {
+        for field in [UsageField::Rss, UsageField::PssDirty, UsageField::Locked] {
+            assert_eq!(usage.get_field(field.key()), Some(field.get(&usage)));
+        }
+    }
+
+    let mut heap = usages
+        .iter()
+        .cloned()
+        .map(|usage| Ranked::by(UsageField::Rss, usage))
+        .collect::<BinaryHeap<_>>();
+    let largest = usages.iter().map(|usage| usage.rss).max().unwrap();
+    assert_eq!(heap.pop().unwrap().item.rss, largest);
+}
+
+#[test]
+fn usage_delta() {
+    let earlier = Usage {
+        rss: 64 << 10,
+        pss: 32 << 10,
+        swap: 4 << 10,
+        thp_eligible: Some(false),
+        protection_key: Some(0),
+        vm_flags: Some(VmFlags::RD | VmFlags::WR | VmFlags::HG),
+        ..Usage::default()
+    };
+    let later = Usage {
+        rss: 16 << 10,
+        pss: 48 << 10,
+        thp_eligible: Some(true),
+        protection_key: Some(0),
+        vm_flags: Some(VmFlags::RD | VmFlags::WR | VmFlags::NH),
+        ..Usage::default()
+    };
+
+    let delta = later.delta(&earlier);
+    assert_eq!(delta.rss, -(48 << 10));
+    assert_eq!(delta.pss, 16 << 10);
+    assert_eq!(delta.swap, -(4 << 10));
+    assert_eq!(delta.size, 0);
+    assert!(delta.thp_eligible_changed);
+    assert!(!delta.protection_key_changed);
+    assert_eq!(delta.vm_flags_added, VmFlags::NH);
+    assert_eq!(delta.vm_flags_removed, VmFlags::HG);
+
+    let reverse = earlier.delta(&later);
+    assert_eq!(reverse.rss, 48 << 10);
+    assert_eq!(reverse.vm_flags_added, VmFlags::HG);
+    assert_eq!(later.delta(&later), smaps::UsageDelta::default());
+}
+
+#[test]
+fn display_table() {
+    let snapshot = Snapshot::from(sample());
+    let header = format!(
+        "{:<33}  {:<4}  {:>10}  {:>10}  Path",
+        "Address", "Perm", "Size", "PSS"
+    );
+    let path_column = header.find("Path").unwrap();
+
+    let start = |row: &str| u64::from_str_radix(&row[..16], 16).unwrap();
+    let table = snapshot.display_table().to_string();
+    let mut lines = table.lines();
+    assert_eq!(lines.next(), Some(header.as_str()));
+    let rows = lines.collect::<Vec<_>>();
+    assert_eq!(rows.len(), snapshot.len());
+    for (row, (mapping, _)) in rows.iter().zip(&snapshot) {
+        assert_eq!(start(row), mapping.start);
+        assert_eq!(&row[33..35], "  ");
+        assert_eq!(&row[35..39], mapping.permissions.to_string());
+        let path = mapping.path.as_deref().unwrap_or("[anon]");
+        assert!(row[path_column..].starts_with(path), "{}", row);
+    }
+
+    let table = snapshot.display_table().sort_by_pss().to_string();
+    let pss = table
+        .lines()
+        .skip(1)
+        .map(|row| snapshot.find(start(row)).unwrap().1.pss)
+        .collect::<Vec<_>>();
+    assert_eq!(pss.len(), snapshot.len());
+    assert!(pss.windows(2).all(|pair| pair[0] >= pair[1]));
+}
+
+#[test]
+fn diff_snapshots() {
+    let earlier = Snapshot::from(sample());
+    let mut later = earlier.clone();
+    later.retain(|(mapping, _)| mapping.path.as_deref() != Some("/tmp/scratch.bin"));
+
+    let mut regions = Vec::from(later);
+    let heap = regions
+        .iter_mut()
+        .find(|(mapping, _)| mapping.path.as_deref() == Some("[heap]"))
+        .unwrap();
+    heap.1.pss += 64 << 10;
+    let (mut mapping, mut usage) = heap.clone();
+    mapping.start = 0x10000;
+    mapping.end = 0x20000;
+    mapping.path = None;
+    usage.pss = 16 << 10;
+    regions.push((mapping, usage));
+    let later = Snapshot::from(regions);
+
+    let report = earlier.diff(&later);
+    assert_eq!(report.added().len(), 1);
+    assert_eq!(report.removed().len(), 1);
+    assert_eq!(
+        report.removed()[0].0.path.as_deref(),
+        Some("/tmp/scratch.bin")
+    );
+    assert_eq!(report.grown().len(), 1);
+    assert_eq!(report.grown()[0].0.path.as_deref(), Some("[heap]"));
+    assert!(report.shrunk().is_empty());
+    assert_eq!(
+        report.total_pss_change(),
+        later.total_pss() as i64 - earlier.total_pss() as i64
+    );
+
+    let display = report.display().to_string();
+    assert!(display.starts_with("PSS +"), "{}", display);
+    assert!(display.contains("[heap]"), "{}", display);
+
+    let mut rebased = earlier.clone();
+    rebased.rebase();
+    let report = earlier.diff_by(&rebased, MatchBy::Path);
+    let anonymous = earlier
+        .iter()
+        .filter(|(mapping, _)| mapping.path.is_none())
+        .count();
+    assert_eq!(report.added().len(), anonymous);
+    assert_eq!(report.removed().len(), anonymous);
+    assert!(report.grown().is_empty());
+    assert_eq!(earlier.diff(&rebased).added().len(), earlier.len());
+}
+
+#[test]
+fn changes_between_regions() {
+    let before = sample();
+    let mut after = before.clone();
+    let scratch = after
+        .iter()
+        .position(|(mapping, _)| mapping.path.as_deref() == Some("/tmp/scratch.bin"))
+        .unwrap();
+    let (removed, removed_usage) = after.remove(scratch);
+
+    let heap = after
+        .iter_mut()
+        .find(|(mapping, _)| mapping.path.as_deref() == Some("[heap]"))
+        .unwrap();
+    let heap_before = heap.1.clone();
+    heap.1.pss += 64 << 10;
+    let heap_after = heap.1.clone();
+
+    // Growing in place changes the key, so it's reported as removed and added
+    let (mut grown, usage) = after[0].clone();
+    grown.end += 0x1000;
+    after[0] = (grown.clone(), usage.clone());
+
+    let report = changes(&before, &after);
+    assert_eq!(report.len(), 4, "{:#?}", report);
+    assert!(report.contains(&Change::Removed(removed, removed_usage)));
+    assert!(report.contains(&Change::Removed(before[0].0.clone(), before[0].1.clone())));
+    assert!(report.contains(&Change::Modified {
+        mapping: after
+            .iter()
+            .find(|(mapping, _)| mapping.path.as_deref() == Some("[heap]"))
+            .unwrap()
+            .0
+            .clone(),
+        before: heap_before,
+        after: heap_after,
+    }));
+    assert_eq!(report.last(), Some(&Change::Added(grown, usage)));
+
+    assert!(changes(&before, &before).is_empty());
+}
+
+#[test]
+fn hash_collections() {
+    let entries = sample();
+    let by_mapping = entries.iter().cloned().collect::<HashMap<Mapping, Usage>>();
+    assert_eq!(by_mapping.len(), entries.len());
+    for (mapping, usage) in &entries {
+        assert_eq!(by_mapping[mapping], *usage);
+    }
+
+    let usages = entries
+        .iter()
+        .chain(&entries)
+        .map(|(_, usage)| usage.clone())
+        .collect::<HashSet<_>>();
+    assert!(usages.len() <= entries.len());
+    assert!(entries.iter().all(|(_, usage)| usages.contains(usage)));
+
+    let devices = entries
+        .iter()
+        .map(|(mapping, _)| mapping.device)
+        .collect::<HashSet<_>>();
+    assert!(devices.iter().any(|device| device.is_anonymous()));
+}